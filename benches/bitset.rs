@@ -0,0 +1,92 @@
+//! Compares scoring day 4 cards with `HashSet`s, as day 4 used to, and with `SmallBitSet`s.
+//! Run with `cargo bench --bench bitset`.
+use std::collections::HashSet;
+
+use advent_of_code::bitset::SmallBitSet;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Cards shaped like the puzzle input: 10 winning numbers and 25 numbers below 100.
+fn cards() -> Vec<(Vec<usize>, Vec<usize>)> {
+    // a small linear congruential generator, so that the cards are the same on every run.
+    let mut seed: u64 = 2023;
+    let mut next = move || {
+        seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+        (seed >> 33) as usize % 99 + 1
+    };
+
+    (0..200)
+        .map(|_| {
+            let winning = (0..10).map(|_| next()).collect();
+            let numbers = (0..25).map(|_| next()).collect();
+            (winning, numbers)
+        })
+        .collect()
+}
+
+fn points(wins: usize) -> u32 {
+    if wins == 0 {
+        0
+    } else {
+        1 << (wins - 1)
+    }
+}
+
+fn bench(c: &mut Criterion) {
+    let cards = cards();
+    let hash_sets: Vec<(Vec<usize>, HashSet<usize>)> = cards
+        .iter()
+        .map(|(winning, numbers)| (winning.clone(), numbers.iter().copied().collect()))
+        .collect();
+    let bit_sets: Vec<(SmallBitSet, SmallBitSet)> = cards
+        .iter()
+        .map(|(winning, numbers)| {
+            (
+                winning.iter().copied().collect(),
+                numbers.iter().copied().collect(),
+            )
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("day 4 points");
+    group.bench_function("hash set", |b| {
+        b.iter(|| {
+            black_box(&hash_sets)
+                .iter()
+                .map(|(winning, numbers)| {
+                    points(winning.iter().filter(|v| numbers.contains(v)).count())
+                })
+                .sum::<u32>()
+        })
+    });
+    group.bench_function("small bit set", |b| {
+        b.iter(|| {
+            black_box(&bit_sets)
+                .iter()
+                .map(|&(winning, numbers)| points((winning & numbers).len()))
+                .sum::<u32>()
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("day 4 parse sets");
+    group.bench_function("hash set", |b| {
+        b.iter(|| {
+            black_box(&cards)
+                .iter()
+                .map(|(_, numbers)| numbers.iter().copied().collect::<HashSet<_>>().len())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("small bit set", |b| {
+        b.iter(|| {
+            black_box(&cards)
+                .iter()
+                .map(|(_, numbers)| numbers.iter().copied().collect::<SmallBitSet>().len())
+                .sum::<usize>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
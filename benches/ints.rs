@@ -0,0 +1,124 @@
+//! Compares the byte-level integer parsers in `parse::bytes` with `str::parse` on the real day 4 and 5 inputs,
+//! timed by the same harness as `cargo solve --time`. Days whose input has not been downloaded are skipped.
+//! Run with `cargo bench --bench ints`.
+use std::fs;
+
+use advent_of_code::{
+    day, parse,
+    parse::bytes,
+    template::{runner::run_bench, Day, ANSI_BOLD, ANSI_RESET},
+};
+
+/// The real input of `day`, or `None` with a note if it has not been downloaded.
+fn read_input(day: Day) -> Option<String> {
+    let path = day.input_path();
+    match fs::read_to_string(&path) {
+        Ok(input) => Some(input),
+        Err(_) => {
+            println!("Skipping day {day}: \"{path}\" not found. Run `cargo download {day}` first.");
+            None
+        }
+    }
+}
+
+fn heading(title: &str) {
+    println!("\n{ANSI_BOLD}{title}{ANSI_RESET}");
+}
+
+fn bench_day_5(almanac: &str) {
+    heading("All integers in day 5");
+    run_bench(
+        "str::parse",
+        |s: &str| {
+            s.split(|c: char| !c.is_ascii_digit())
+                .filter(|token| !token.is_empty())
+                .map(|token| token.parse::<u64>().unwrap())
+                .fold(0, u64::wrapping_add)
+        },
+        almanac,
+    );
+    run_bench(
+        "parse::ints",
+        |s: &str| {
+            s.lines()
+                .flat_map(|line| parse::ints::<u64>(line).unwrap())
+                .fold(0, u64::wrapping_add)
+        },
+        almanac,
+    );
+    run_bench(
+        "bytes::ints",
+        |s: &str| bytes::ints::<u64>(s.as_bytes()).fold(0, u64::wrapping_add),
+        almanac,
+    );
+
+    heading("Single u64 tokens in day 5");
+    let tokens: Vec<&str> = almanac
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|token| !token.is_empty())
+        .collect();
+    run_bench(
+        "str::parse",
+        |tokens: &[&str]| {
+            tokens
+                .iter()
+                .map(|token| token.parse::<u64>().unwrap())
+                .fold(0, u64::wrapping_add)
+        },
+        &tokens[..],
+    );
+    run_bench(
+        "bytes::parse_u64",
+        |tokens: &[&str]| {
+            tokens
+                .iter()
+                .map(|token| bytes::parse_u64(token.as_bytes()).unwrap())
+                .fold(0, u64::wrapping_add)
+        },
+        &tokens[..],
+    );
+}
+
+fn bench_day_4(cards: &str) {
+    heading("Card numbers in day 4");
+    // the numbers after the card id, which are right-aligned in columns of 3 bytes.
+    let numbers: Vec<&str> = cards
+        .lines()
+        .filter_map(|line| Some(line.split_once(':')?.1))
+        .collect();
+    run_bench(
+        "str::parse",
+        |numbers: &[&str]| {
+            numbers
+                .iter()
+                .flat_map(|line| line.split_whitespace())
+                .filter_map(|token| token.parse::<u32>().ok())
+                .sum::<u32>()
+        },
+        &numbers[..],
+    );
+    run_bench(
+        "bytes::fixed_width",
+        |numbers: &[&str]| {
+            numbers
+                .iter()
+                .flat_map(|line| {
+                    line.as_bytes()
+                        .split(|&b| b == b'|')
+                        .flat_map(|half| bytes::fixed_width::<u32>(half, 3))
+                })
+                .flatten()
+                .sum::<u32>()
+        },
+        &numbers[..],
+    );
+}
+
+fn main() {
+    if let Some(almanac) = read_input(day!(5)) {
+        bench_day_5(&almanac);
+    }
+    if let Some(cards) = read_input(day!(4)) {
+        bench_day_4(&cards);
+    }
+}
@@ -0,0 +1,95 @@
+//! Compares `MultiMatcher` with trying every window of a line, as day 1 used to.
+//! Run with `cargo bench --bench text`.
+use advent_of_code::text::MultiMatcher;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// A day 1 style input: lines of letters with digits and spelled-out digits mixed in.
+fn input() -> String {
+    let filler = ["abc", "xyz", "qrst", "mn", "pkl"];
+    (0..1000)
+        .map(|i: usize| {
+            let mut line = String::new();
+            for j in 0..6 {
+                line.push_str(filler[(i + j) % filler.len()]);
+                if (i + j).is_multiple_of(3) {
+                    line.push(char::from(b'0' + ((i * j) % 10) as u8));
+                } else {
+                    line.push_str(WORDS[(i + 7 * j) % WORDS.len()]);
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Allocates a string for each window of 3 to 5 characters and compares it with the words.
+fn windows(input: &str) -> u32 {
+    input
+        .lines()
+        .map(|line| {
+            let chars: Vec<char> = line.chars().collect();
+            let digits: Vec<u32> = (0..chars.len())
+                .filter_map(|i| {
+                    if let Some(d) = chars[i].to_digit(10) {
+                        return Some(d);
+                    }
+                    (3..=5)
+                        .filter(|len| i + len <= chars.len())
+                        .map(|len| String::from_iter(&chars[i..i + len]))
+                        .find_map(|word| WORDS.iter().position(|w| *w == word))
+                        .map(|d| d as u32)
+                })
+                .collect();
+            digits.first().unwrap_or(&0) * 10 + digits.last().unwrap_or(&0)
+        })
+        .sum()
+}
+
+fn matcher(input: &str, matcher: &MultiMatcher<u32>) -> u32 {
+    input
+        .lines()
+        .map(|line| {
+            let first = matcher
+                .find_leftmost(line.as_bytes())
+                .map_or(0, |m| *m.value);
+            let last = matcher
+                .find_rightmost(line.as_bytes())
+                .map_or(0, |m| *m.value);
+            first * 10 + last
+        })
+        .sum()
+}
+
+fn bench(c: &mut Criterion) {
+    let input = input();
+    let digits = MultiMatcher::new(
+        (b'0'..=b'9')
+            .map(|d| vec![d])
+            .zip(0..)
+            .chain(WORDS.iter().map(|w| w.as_bytes().to_vec()).zip(0..)),
+    );
+    assert_eq!(windows(&input), matcher(&input, &digits));
+
+    let mut group = c.benchmark_group("day 1 digits");
+    group.bench_function("windows", |b| b.iter(|| windows(black_box(&input))));
+    group.bench_function("multi matcher", |b| {
+        b.iter(|| matcher(black_box(&input), &digits))
+    });
+    group.bench_function("multi matcher, overlapping", |b| {
+        b.iter(|| {
+            black_box(&input)
+                .lines()
+                .map(|line| digits.find_overlapping(line.as_bytes()).count())
+                .sum::<usize>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
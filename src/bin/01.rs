@@ -1,87 +1,53 @@
-pub fn parse_part_one(_input: &str) -> Vec<String>  {
-    _input.lines()
-        .map(|line| line.chars().filter(|c| c.is_digit(10)).collect::<Vec<char>>())
-        .map(|line| {
-            let first: &char = line.first().unwrap();
-            let last: &char = line.last().unwrap();
-
-            let mut result: String = String::new();
-            result.push(*first);
-            result.push(*last);
+use advent_of_code::text::MultiMatcher;
+
+const WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Matches the digits `0`-`9`, and their spelled-out names if `spelled` is set.
+fn digits(spelled: bool) -> MultiMatcher<u32> {
+    let words: &[&str] = if spelled { &WORDS } else { &[] };
+    let numerals = (b'0'..=b'9').map(|d| vec![d]);
+    let words = words.iter().map(|w| w.as_bytes().to_vec());
+    MultiMatcher::new(numerals.zip(0..).chain(words.zip(0..)))
+}
 
-            result
-        }).collect()
+/// The first and last digit of each line, as a two-digit string.
+fn calibration_values(input: &str, matcher: &MultiMatcher<u32>) -> Vec<String> {
+    input
+        .lines()
+        .filter_map(|line| {
+            let first = matcher.find_leftmost(line.as_bytes())?;
+            let last = matcher.find_rightmost(line.as_bytes())?;
+            Some(format!("{}{}", first.value, last.value))
+        })
+        .collect()
 }
 
+pub fn parse_part_one(_input: &str) -> Vec<String> {
+    calibration_values(_input, &digits(false))
+}
 
 pub fn part_one(_input: &str) -> Option<u32> {
-
     let data = parse_part_one(_input);
-    Some(data.iter().map(|line| (*line).parse::<u32>().unwrap()).sum())
+    Some(
+        data.iter()
+            .map(|line| (*line).parse::<u32>().unwrap())
+            .sum(),
+    )
 }
 
-
-
 pub fn parse_part_two(_input: &str) -> Vec<String> {
-    let res: String = _input.lines().map(|line| {
-        let chars = line.chars().collect::<Vec<char>>();
-        let mut res = String::new();
-        let mut i = 0;
-        while i < chars.len() {
-            let c = chars[i];
-            if c.is_digit(10) {
-                res.push(c);
-                i += 1;
-                continue
-            }
-            let mut found = false;
-            for s in 3..=5 {
-                let j = i+s;
-                if j > chars.len() {
-                    break
-                }
-                let word = String::from_iter(chars[i..j].iter());
-
-                let num = match word.as_str() {
-                    "zero" => Some('0'),
-                    "one" => Some('1'),
-                    "two" => Some('2'),
-                    "three" => Some('3'),
-                    "four" => Some('4'),
-                    "five" => Some('5'),
-                    "six" => Some('6'),
-                    "seven" => Some('7'),
-                    "eight" => Some('8'),
-                    "nine" => Some('9'),
-                    _ => None
-                };
-
-                match num {
-                    Some(n) => {
-                        res.push(n);
-                        i += s-1;
-                        found = true;
-                        break
-                    },
-                    None => {
-                        continue
-                    }
-                }
-            }
-
-            if !found {
-                i += 1
-            }
-        }
-        res
-    }).collect::<Vec<String>>().join("\n");
-
-    parse_part_one(res.as_str())
+    calibration_values(_input, &digits(true))
 }
 
 pub fn part_two(_input: &str) -> Option<u32> {
     let data = parse_part_two(_input);
-    Some(data.iter().map(|line| (*line).parse::<u32>().unwrap()).sum())
+    Some(
+        data.iter()
+            .map(|line| (*line).parse::<u32>().unwrap())
+            .sum(),
+    )
 }
 
 advent_of_code::main!(1);
@@ -92,7 +58,7 @@ mod tests {
 
     #[test]
     fn test_parse_part_one() {
-        let result = parse_part_one(&advent_of_code::template::read_file("examples", 1));
+        let result = parse_part_one(&advent_of_code::template::read_file("examples", DAY));
         let mut correct_result = Vec::new();
         correct_result.push("12");
         correct_result.push("38");
@@ -103,7 +69,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", 1));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(142));
     }
 
@@ -1,79 +1,96 @@
-use std::collections::HashMap;
+use advent_of_code::{intern::Interner, parse};
 
+/// Cube counts of one reveal, indexed by colour id.
+type Reveal = Vec<u32>;
+
+#[derive(Debug)]
 struct Game {
     id: u32,
-    reveals: Vec<HashMap<String, u32>>
+    reveals: Vec<Reveal>,
+}
+
+/// Ids of the colours interned by `colours()`.
+const RED: u32 = 0;
+const GREEN: u32 = 1;
+const BLUE: u32 = 2;
+
+fn colours() -> Interner {
+    ["red", "green", "blue"].into_iter().collect()
+}
+
+fn count(reveal: &Reveal, colour: u32) -> u32 {
+    reveal.get(colour as usize).copied().unwrap_or(0)
 }
 
 impl Game {
-    pub fn from_str(_input: &str) -> Game {
-        let (game, rest) = _input.split_once(":").expect("Failed to find ':'");
-        let game_id = game.split_once(" ").unwrap().1.parse::<u32>().unwrap();
-        let reveals: Vec<HashMap<String, u32>> = rest.split(";")
+    pub fn from_str<'a>(line: &'a str, colours: &mut Interner) -> parse::Result<'a, Game> {
+        let (game, rest) = parse::split_once(line, ":")?;
+        let game_id = parse::int(parse::tag(game, "Game ")?)?;
+        let reveals = rest
+            .split(';')
             .map(|group| {
-                group.split(",").map(|item| {
-                    let (count, color) = item.trim().split_once(" ").unwrap();
-                    (color.to_string(), count.parse::<u32>().unwrap())
-                }).collect()
-            }).collect();
+                let mut reveal = vec![0; colours.len()];
+                for (count, colour) in parse::key_values(group, ",", " ")? {
+                    let colour = colours.intern(colour) as usize;
+                    if colour >= reveal.len() {
+                        reveal.resize(colour + 1, 0);
+                    }
+                    reveal[colour] = parse::int(count)?;
+                }
+                Ok(reveal)
+            })
+            .collect::<parse::Result<Vec<Reveal>>>()?;
 
-        Game {
+        Ok(Game {
             id: game_id,
             reveals,
-        }
+        })
     }
 
-    fn is_possible(&self, limits: &HashMap<&str, u32>) -> bool {
-        for reveal in &self.reveals {
-            for (color, count) in limits {
-                match reveal.get(*color) {
-                    Some(cnt) => {
-                        if *cnt > *count {
-                            return false;
-                        }
-                    }
-                    None => {}
-                }
-            }
-        }
-        true
+    fn is_possible(&self, limits: &[(u32, u32)]) -> bool {
+        self.reveals.iter().all(|reveal| {
+            limits
+                .iter()
+                .all(|&(colour, limit)| count(reveal, colour) <= limit)
+        })
     }
 
     fn power(&self) -> u32 {
-        ["red", "green", "blue"].map(|color| {
-            self.reveals.iter()
-                .map(|reveal| reveal.get(color))
-                .filter(Option::is_some)
-                .map(Option::unwrap)
-                .max().unwrap().clone()
-        }).iter().copied().reduce(|x, y| x * y).unwrap()
+        [RED, GREEN, BLUE]
+            .map(|colour| {
+                self.reveals
+                    .iter()
+                    .map(|reveal| count(reveal, colour))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .iter()
+            .product()
     }
 }
 
+fn parse_games(input: &str) -> Vec<Game> {
+    let mut colours = colours();
+    parse::lines(input, |line| Game::from_str(line, &mut colours)).unwrap()
+}
+
 pub fn part_one(_input: &str) -> Option<u32> {
-    let filters: HashMap<&str, u32> = [
-        ("red", 12),
-        ("green", 13),
-        ("blue", 14),
-    ].into_iter().collect();
-
-    let sum = _input
-        .lines()
-        .map(Game::from_str)
-        .filter(|game| game.is_possible(&filters))
+    let limits = [(RED, 12), (GREEN, 13), (BLUE, 14)];
+
+    let sum = parse_games(_input)
+        .into_iter()
+        .filter(|game| game.is_possible(&limits))
         .map(|game| game.id)
         .sum();
     Some(sum)
 }
 
 pub fn part_two(_input: &str) -> Option<u32> {
-    let sum = _input
-        .lines()
-        .map(Game::from_str)
+    let sum = parse_games(_input)
+        .into_iter()
         .map(|game| game.power())
         .sum();
     Some(sum)
-
 }
 
 advent_of_code::main!(2);
@@ -84,26 +101,40 @@ mod tests {
 
     #[test]
     fn test_parse_game() {
-        let result = Game::from_str("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        let result = Game::from_str(
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            &mut colours(),
+        )
+        .unwrap();
         assert_eq!(result.id, 1);
         assert_eq!(result.reveals.len(), 3);
-        assert_eq!(result.reveals[0]["blue"], 3);
-        assert_eq!(result.reveals[0]["red"], 4);
-        assert_eq!(result.reveals[1]["red"], 1);
-        assert_eq!(result.reveals[1]["green"], 2);
-        assert_eq!(result.reveals[1]["blue"], 6);
-        assert_eq!(result.reveals[2]["green"], 2);
+        assert_eq!(count(&result.reveals[0], BLUE), 3);
+        assert_eq!(count(&result.reveals[0], RED), 4);
+        assert_eq!(count(&result.reveals[0], GREEN), 0);
+        assert_eq!(count(&result.reveals[1], RED), 1);
+        assert_eq!(count(&result.reveals[1], GREEN), 2);
+        assert_eq!(count(&result.reveals[1], BLUE), 6);
+        assert_eq!(count(&result.reveals[2], GREEN), 2);
+    }
+
+    #[test]
+    fn test_parse_game_error() {
+        let input = "Game 1: 3 blue\nGame 2: 3 blue, red";
+        let mut colours = colours();
+        let error = parse::lines(input, |line| Game::from_str(line, &mut colours)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 17));
+        assert_eq!(error.found, "red");
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", 2));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", 2));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2286));
     }
 }
@@ -1,86 +1,39 @@
-pub fn parse_input(_input: &str) -> Vec<Vec<char>>{
-    _input.lines().map(|x| x.chars().collect()).collect()
+use advent_of_code::grid::{tokens::Tokens, Grid};
+
+pub fn parse_input(input: &str) -> Grid<char> {
+    input.parse().expect("schematic should be rectangular")
 }
 
-pub fn count_score(data: &Vec<Vec<char>>, loc: (usize, (usize, usize))) -> u32 {
-    let number = String::from_iter(data[loc.0][loc.1.0..=loc.1.1].iter()).parse::<u32>().unwrap();
-    let width = data.get(0).unwrap().len();
-    let mut rows = Vec::from([loc.0]);
-    if loc.0 > 0 {
-        rows.push(loc.0-1)
-    }
-    if loc.0 < data.len()-1 {
-        rows.push(loc.0+1)
-    }
+fn is_symbol(c: char) -> bool {
+    !c.is_ascii_digit() && c != '.'
+}
 
-    let symbols: usize = rows.into_iter().map(|row_index| {
-        let coordinates: Vec<(usize, usize)> = if row_index == loc.0 {
-            let mut cols = Vec::new();
-            if loc.1.0 > 0 {
-                cols.push((row_index, loc.1.0 -1))
-            }
-            if loc.1.1 < data.len()-1 {
-                cols.push((row_index, loc.1.1 + 1))
-            }
-            cols
-        } else {
-            let min = if loc.1.0 > 0 { loc.1.0 - 1 } else { loc.1.0 };
-            let max = if loc.1.1 < width-1 { loc.1.1 + 1 } else { loc.1.1 };
-            (min..=max)
-                .map(|x| (row_index, x))
-                .collect::<Vec<(usize, usize)>>()
-        };
-        let characters: Vec<char> = coordinates.into_iter().filter(|(_, ci)| {
-            *ci < width
-        }).map(|(ri, ci)| data[ri][ci]).collect();
+pub fn part_one(input: &str) -> Option<u32> {
+    let grid = parse_input(input);
+    let numbers = Tokens::numbers(&grid);
 
-        characters.into_iter().filter(|c| {
-            match *c {
-                '0'..='9' => false,
-                '.' => false,
-                _ => true,
-            }
-        }).count()
-    }).sum();
+    let sum = numbers
+        .iter()
+        .filter(|number| numbers.border(number).any(|pos| is_symbol(grid[pos])))
+        .filter_map(|number| number.value::<u32>())
+        .sum();
 
-    if symbols > 0 {
-        number
-    } else {
-        0
-    }
+    Some(sum)
 }
 
-pub fn part_one(_input: &str) -> Option<u32> {
-    let data = parse_input(_input);
-    let width = data[0].len();
-    let mut sum: u32 = 0;
-    for (row_index, row) in data.iter().enumerate() {
-        let mut i = 0;
-        let mut j = 0;
-        let mut search = true;
-        for (column_index, col) in row.iter().enumerate() {
-            if col.is_digit(10) {
-                if search {
-                    search = false;
-                    i = column_index;
-                }
-                j = column_index;
-                if column_index + 1 < width {
-                    continue
-                }
-            }
+pub fn part_two(input: &str) -> Option<u32> {
+    let grid = parse_input(input);
+    let numbers = Tokens::numbers(&grid);
 
-            if !search {
-                sum += count_score(&data, (row_index, (i, j)));
-                search = true;
-            }
-        }
-    }
-    Some(sum)
-}
+    let sum = grid
+        .positions(|&c| c == '*')
+        .filter_map(|pos| match numbers.adjacent_to(pos)[..] {
+            [a, b] => Some(a.value::<u32>()? * b.value::<u32>()?),
+            _ => None,
+        })
+        .sum();
 
-pub fn part_two(_input: &str) -> Option<u32> {
-    None
+    Some(sum)
 }
 
 advent_of_code::main!(3);
@@ -91,19 +44,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", 3));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(4361));
     }
 
     #[test]
     fn test_part_one_input() {
-        let result = part_one(&advent_of_code::template::read_file("inputs", 3));
+        let result = part_one(&advent_of_code::template::read_file("inputs", DAY));
         assert_eq!(result, Some(535235));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", 3));
-        assert_eq!(result, None);
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(467835));
     }
 }
@@ -1,44 +1,45 @@
-use std::collections::{HashSet};
+use advent_of_code::{bitset::SmallBitSet, parse};
 
 #[derive(PartialEq, Debug)]
 struct Card {
     num: u32,
-    winning_numbers: Vec<u32>,
-    numbers: HashSet<u32>,
+    winning_numbers: SmallBitSet,
+    numbers: SmallBitSet,
+}
+
+fn number_set(s: &str) -> parse::Result<'_, SmallBitSet> {
+    let values: Vec<usize> = parse::ints(s)?;
+    if values.iter().any(|&v| v >= <SmallBitSet>::CAPACITY) {
+        return Err(parse::Error::new(s, "numbers below 128"));
+    }
+    Ok(values.into_iter().collect())
 }
 
 impl Card {
-    pub fn from_str(s: &str) -> Card {
-        let (first, second) = s.split_once(":").expect("Missing ':'");
-        let card_num: u32 = first
-            .split(" ").last().expect("Should a last one")
-            .parse().expect("Failed to parse number");
-        let (winners, numbers) = second.split_once("|").expect("Failed split");
+    pub fn from_str(s: &str) -> parse::Result<'_, Card> {
+        let (first, second) = parse::split_once(s, ":")?;
+        let card_num = parse::int(parse::tag(first, "Card")?)?;
+        let (winners, numbers) = parse::split_once(second, "|")?;
 
-        Card {
+        Ok(Card {
             num: card_num,
-            winning_numbers: winners.trim().split(' ')
-                .filter(|x| x.len() > 0)
-                .map(|n| n.parse().expect("Failed parse")).collect(),
-            numbers: numbers.trim().split(' ')
-                .filter(|x| x.len() > 0)
-                .map(|n| {
-                    n.parse().expect("Failed parse")
-                }).collect(),
-        }
+            winning_numbers: number_set(winners)?,
+            numbers: number_set(numbers)?,
+        })
     }
 
     pub fn points(&self) -> u32 {
-        let wins = self.winning_numbers.iter().filter(|v| self.numbers.contains(v)).count();
+        let wins = (self.winning_numbers & self.numbers).len();
         if wins == 0 {
-            return 0
+            return 0;
         }
         2u32.pow((wins as u32) - 1)
     }
 }
 
 pub fn part_one(_input: &str) -> Option<u32> {
-    Some(_input.lines().map(Card::from_str).map(|c| c.points()).sum())
+    let cards = parse::lines(_input, Card::from_str).unwrap();
+    Some(cards.iter().map(Card::points).sum())
 }
 
 pub fn part_two(_input: &str) -> Option<u32> {
@@ -53,41 +54,43 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", 4));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_card_parse() {
-        let result = Card::from_str("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        let result = Card::from_str("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
         let valid_card = Card {
             num: 1,
-            winning_numbers: Vec::from([41, 48, 83, 86, 17]),
-            numbers: HashSet::from([83, 86, 6, 31, 17, 9, 48, 53])
+            winning_numbers: SmallBitSet::from_iter([41, 48, 83, 86, 17]),
+            numbers: SmallBitSet::from_iter([83, 86, 6, 31, 17, 9, 48, 53]),
         };
         assert_eq!(result, valid_card);
+
+        assert!(Card::from_str("Card 2: 41 128 | 83").is_err());
     }
 
     #[test]
     fn test_card_points() {
         let card = Card {
             num: 1,
-            winning_numbers: Vec::from([41, 48, 83, 86, 17]),
-            numbers: HashSet::from([83, 86, 6, 31, 17, 9, 48, 53])
+            winning_numbers: SmallBitSet::from_iter([41, 48, 83, 86, 17]),
+            numbers: SmallBitSet::from_iter([83, 86, 6, 31, 17, 9, 48, 53]),
         };
         assert_eq!(card.points(), 8);
 
         let losing_card = Card {
             num: 1,
-            winning_numbers: Vec::from([31, 48, 13, 56, 72]),
-            numbers: HashSet::from([74, 77, 10, 23, 35, 67, 36, 11])
+            winning_numbers: SmallBitSet::from_iter([31, 48, 13, 56, 72]),
+            numbers: SmallBitSet::from_iter([74, 77, 10, 23, 35, 67, 36, 11]),
         };
         assert_eq!(losing_card.points(), 0);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", 4));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, None);
     }
 }
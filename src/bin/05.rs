@@ -1,87 +1,141 @@
-use std::ops::Range;
-use rayon::prelude::*;
+use advent_of_code::{
+    intern::Interner,
+    parse,
+    piecewise::{MapError, PiecewiseMap},
+};
 
+/// A map between two categories, identified by their interned names.
 struct SoilMap {
-    from: String,
-    to: String,
-    ranges: Vec<(u64, u64, u64)>
+    from: u32,
+    to: u32,
+    map: PiecewiseMap,
+}
+
+struct Almanac {
+    seeds: Vec<i64>,
+    maps: Vec<SoilMap>,
+    categories: Interner,
 }
 
 impl SoilMap {
-    pub fn from_str(data: &str) -> SoilMap {
-        let lines: Vec<&str> = data.lines().collect();
-        let first = *lines.first().expect("No items");
-        let (from, to) = first
-            .split_once(' ')
-            .expect("No space found")
-            .0
-            .split_once("-to-")
-            .expect("Should find two parts");
-
-        let mut ranges = lines[1..].into_iter()
-            .map(|&range| {
-                let items: Vec<u64> = range.split(" ").map(|x| x.parse::<u64>().unwrap()).collect();
-                (items[1], items[1]+items[2], items[0])
+    pub fn from_str<'a>(data: &'a str, categories: &mut Interner) -> parse::Result<'a, SoilMap> {
+        let (header, ranges) = data.split_once('\n').unwrap_or((data, ""));
+        let (name, _) = parse::split_once(header, " map:")?;
+        let (from, to) = parse::split_once(name, "-to-")?;
+
+        let lines: Vec<&str> = ranges.lines().collect();
+        let triples = lines
+            .iter()
+            .map(|&line| match parse::ints(line)?[..] {
+                [dest, src, len] => Ok((dest, src, len)),
+                _ => Err(parse::Error::new(line, "\"dest src len\"")),
             })
-            .collect::<Vec<(u64, u64, u64)>>();
+            .collect::<parse::Result<Vec<_>>>()?;
+
+        let map = PiecewiseMap::from_triples(triples.iter().copied()).map_err(|e| match e {
+            // point at the line of the second range, the first one is named in the message.
+            MapError::Overlap(first, second) => {
+                let line = lines
+                    .iter()
+                    .zip(&triples)
+                    .find(|(_, &(_, src, len))| (src..src + len) == second)
+                    .map_or(ranges, |(&line, _)| line);
+                parse::Error::new(
+                    line,
+                    format!("a source range that does not overlap {first:?}"),
+                )
+            }
+            MapError::Overflow(dest, src, len) => {
+                let line = lines
+                    .iter()
+                    .zip(&triples)
+                    .find(|(_, &triple)| triple == (dest, src, len))
+                    .map_or(ranges, |(&line, _)| line);
+                parse::Error::new(line, format!("ranges that end before {}", i64::MAX))
+            }
+            other => parse::Error::new(ranges, other.to_string()),
+        })?;
+
+        Ok(SoilMap {
+            from: categories.intern(from),
+            to: categories.intern(to),
+            map,
+        })
+    }
+}
 
-        ranges.sort_by(|(sa, _, _), (sb, _, _)| sa.cmp(sb));
+fn parse_input(input: &str) -> parse::Result<'_, Almanac> {
+    let mut sections = parse::split_sections(input);
+    let seeds = parse::ints(parse::tag(sections.next().unwrap_or(input), "seeds:")?)?;
 
-        SoilMap {
-            from: from.to_string(),
-            to: to.to_string(),
-            ranges
-        }
-    }
+    let mut categories = Interner::new();
+    let maps = sections
+        .map(|section| SoilMap::from_str(section, &mut categories))
+        .collect::<parse::Result<_>>()?;
 
-    pub fn map(&self, src: u64) -> u64 {
-        match self.ranges.iter().find(| &(start, end, base)| start <= &src && &src < end) {
-            Some((start, _, base)) => {
-                (src-start)+base
-            },
-            None => src
-        }
-    }
+    Ok(Almanac {
+        seeds,
+        maps,
+        categories,
+    })
 }
 
-fn parse_input(_input: &str) -> (Vec<u64>, Vec<SoilMap>) {
-    let chunks: Vec<&str> = _input.split("\n\n").collect();
-    let seeds: Vec<u64> = chunks[0]
-        .split_once(": ")
-        .unwrap().1
-        .split(" ")
-        .map(|x| x.parse::<u64>().unwrap())
-        .collect();
+/// Composes the chain of maps from seeds to locations into a single map.
+fn compose(almanac: &Almanac) -> PiecewiseMap {
+    let mut by_source: Vec<Option<&SoilMap>> = vec![None; almanac.categories.len()];
+    for map in &almanac.maps {
+        by_source[map.from as usize] = Some(map);
+    }
 
-    let map: Vec<SoilMap> = chunks[1..].into_iter()
-        .map(|&data| SoilMap::from_str(data))
-        .collect();
+    let location = almanac.categories.get("location");
+    let mut category = almanac.categories.get("seed");
+    let mut composed = PiecewiseMap::identity();
+
+    // every map is used at most once, which also stops at loops.
+    for _ in 0..almanac.maps.len() {
+        let Some(source) = category.filter(|&c| Some(c) != location) else {
+            break;
+        };
+        let Some(map) = by_source[source as usize] else {
+            break;
+        };
+        composed = composed.then(&map.map);
+        category = Some(map.to);
+    }
 
-    (seeds, map)
+    assert!(
+        category.is_some() && category == location,
+        "Almanac should map seeds to locations"
+    );
+    composed
 }
 
-fn solve (seed: u64, maps: &Vec<SoilMap>) -> u64 {
-    maps.iter().fold(seed, |acc, map| map.map(acc))
+fn to_answer(location: i64) -> u64 {
+    u64::try_from(location).expect("Locations should not be negative")
 }
 
-pub fn part_one(_input: &str) -> Option<u64> {
-    let (seeds, maps) = parse_input(_input);
-    Some(seeds.into_iter().map(|s| solve(s, &maps)).min().unwrap())
+pub fn part_one(input: &str) -> Option<u64> {
+    let almanac = parse::parse(input, parse_input).unwrap();
+    let seed_to_location = compose(&almanac);
+    almanac
+        .seeds
+        .into_iter()
+        .map(|s| seed_to_location.get(s))
+        .min()
+        .map(to_answer)
 }
 
-pub fn part_two(_input: &str) -> Option<u64> {
-    let (seeds, maps) = parse_input(_input);
-
-    let result = seeds
-        .par_chunks(2)
-        .into_par_iter()
-        .flat_map(|chunk| chunk[0]..(chunk[0]+chunk[1]))
-        .map(|c| {
-            solve(c, &maps)
-        })
-        .min().unwrap();
-
-    Some(result)
+pub fn part_two(input: &str) -> Option<u64> {
+    let almanac = parse::parse(input, parse_input).unwrap();
+    let seed_to_location = compose(&almanac);
+
+    almanac
+        .seeds
+        .chunks(2)
+        .flat_map(|chunk| seed_to_location.map_range(chunk[0]..chunk[0] + chunk[1]))
+        .map(|range| range.start)
+        .min()
+        .map(to_answer)
 }
 
 advent_of_code::main!(5);
@@ -92,26 +146,47 @@ mod tests {
 
     #[test]
     fn test_parse_soil_map() {
-        let result = SoilMap::from_str("seed-to-soil map:\n50 98 2\n52 50 48");
-        assert_eq!(result.from, "seed");
-        assert_eq!(result.to, "soil");
-        assert_eq!(result.ranges.len(), 2);
-        assert_eq!(result.map(1), 1);
-        assert_eq!(result.map(50), 52);
-        assert_eq!(result.map(97), 99);
-        assert_eq!(result.map(98), 50);
-        assert_eq!(result.map(99), 51);
+        let mut categories = Interner::new();
+        let result =
+            SoilMap::from_str("seed-to-soil map:\n50 98 2\n52 50 48", &mut categories).unwrap();
+        assert_eq!(categories.resolve(result.from), Some("seed"));
+        assert_eq!(categories.resolve(result.to), Some("soil"));
+        assert_eq!(result.map.segments().count(), 2);
+        assert_eq!(result.map.get(1), 1);
+        assert_eq!(result.map.get(50), 52);
+        assert_eq!(result.map.get(97), 99);
+        assert_eq!(result.map.get(98), 50);
+        assert_eq!(result.map.get(99), 51);
+    }
+
+    #[test]
+    fn test_parse_overlapping_ranges() {
+        let input = "seeds: 1\n\nseed-to-soil map:\n50 98 2\n52 50 48\n10 90 9";
+        let error = parse_input(input).err().unwrap().locate(input);
+        assert_eq!(error.line, 6);
+        assert_eq!(
+            error.expected,
+            "a source range that does not overlap 50..98"
+        );
+    }
+
+    #[test]
+    fn test_parse_ranges_past_i64_max() {
+        let input = "seeds: 1\n\nseed-to-soil map:\n50 98 2\n0 9223372036854775800 100";
+        let error = parse_input(input).err().unwrap().locate(input);
+        assert_eq!(error.line, 5);
+        assert_eq!(error.expected, "ranges that end before 9223372036854775807");
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", 5));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", 5));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(46));
     }
 }
@@ -0,0 +1,255 @@
+/// A fixed-capacity set of small non-negative integers, stored as bits.
+/// Set operations are a handful of word-wise instructions and `len` is a popcount,
+/// which makes it much faster than a `HashSet` for puzzle values such as card numbers or node ids.
+use std::{
+    fmt::Debug,
+    ops::{BitAnd, BitOr, BitXor, Sub},
+};
+
+const BITS: usize = u64::BITS as usize;
+
+/// A set of values in `0..64 * N`. The default of two words holds values below 128.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SmallBitSet<const N: usize = 2> {
+    words: [u64; N],
+}
+
+impl<const N: usize> Default for SmallBitSet<N> {
+    fn default() -> Self {
+        SmallBitSet { words: [0; N] }
+    }
+}
+
+impl<const N: usize> SmallBitSet<N> {
+    /// Values have to be below this.
+    pub const CAPACITY: usize = BITS * N;
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn locate(value: usize) -> (usize, u64) {
+        assert!(
+            value < Self::CAPACITY,
+            "{value} does not fit into a bit set with capacity {}",
+            Self::CAPACITY
+        );
+        (value / BITS, 1 << (value % BITS))
+    }
+
+    /// Adds `value`, returning whether it was not in the set before.
+    ///
+    /// # Panics
+    /// If `value` is not below [`Self::CAPACITY`].
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = Self::locate(value);
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        added
+    }
+
+    /// Removes `value`, returning whether it was in the set.
+    pub fn remove(&mut self, value: usize) -> bool {
+        if value >= Self::CAPACITY {
+            return false;
+        }
+        let (word, bit) = Self::locate(value);
+        let removed = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        removed
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: usize) -> bool {
+        value < Self::CAPACITY && {
+            let (word, bit) = Self::locate(value);
+            self.words[word] & bit != 0
+        }
+    }
+
+    /// Number of values in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; N];
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        SmallBitSet {
+            words: std::array::from_fn(|i| f(self.words[i], other.words[i])),
+        }
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    /// Values in `self` that are not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    /// Values in exactly one of the sets.
+    #[must_use]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a ^ b)
+    }
+
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    #[must_use]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).is_empty()
+    }
+
+    /// Values in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                // clear the lowest set bit.
+                word &= word - 1;
+                Some(i * BITS + bit)
+            })
+        })
+    }
+
+    /// The smallest value in the set.
+    #[must_use]
+    pub fn min(&self) -> Option<usize> {
+        self.iter().next()
+    }
+}
+
+macro_rules! impl_op {
+    ($trait:ident, $fn:ident, $method:ident) => {
+        impl<const N: usize> $trait for SmallBitSet<N> {
+            type Output = Self;
+
+            fn $fn(self, other: Self) -> Self {
+                self.$method(&other)
+            }
+        }
+    };
+}
+
+impl_op!(BitOr, bitor, union);
+impl_op!(BitAnd, bitand, intersection);
+impl_op!(BitXor, bitxor, symmetric_difference);
+impl_op!(Sub, sub, difference);
+
+impl<const N: usize> Extend<usize> for SmallBitSet<N> {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<const N: usize> FromIterator<usize> for SmallBitSet<N> {
+    /// # Panics
+    /// If a value is not below [`Self::CAPACITY`].
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<const N: usize> Debug for SmallBitSet<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SmallBitSet;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn values() -> impl Strategy<Value = Vec<usize>> {
+        prop::collection::vec(0..SmallBitSet::<2>::CAPACITY, 0..40)
+    }
+
+    fn model(set: SmallBitSet) -> BTreeSet<usize> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn inserts_and_removes_values() {
+        let mut set: SmallBitSet = [3, 64, 127].into_iter().collect();
+        assert!(set.contains(64));
+        assert!(!set.contains(65));
+        assert!(!set.contains(1000));
+        assert!(!set.insert(3));
+        assert!(set.insert(0));
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.min(), Some(0));
+
+        assert!(set.remove(64));
+        assert!(!set.remove(64));
+        assert!(!set.remove(1000));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 3, 127]);
+        assert_eq!(format!("{set:?}"), "{0, 3, 127}");
+
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn supports_other_widths() {
+        let mut set = SmallBitSet::<4>::new();
+        assert_eq!(SmallBitSet::<4>::CAPACITY, 256);
+        set.insert(200);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [200]);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn rejects_values_beyond_the_capacity() {
+        SmallBitSet::<1>::new().insert(64);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_btree_set(a in values(), b in values()) {
+            let (set_a, set_b): (SmallBitSet, SmallBitSet) =
+                (a.iter().copied().collect(), b.iter().copied().collect());
+            let (model_a, model_b): (BTreeSet<usize>, BTreeSet<usize>) =
+                (a.into_iter().collect(), b.into_iter().collect());
+
+            prop_assert_eq!(model(set_a), model_a.clone());
+            prop_assert_eq!(set_a.len(), model_a.len());
+            prop_assert_eq!(model(set_a | set_b), &model_a | &model_b);
+            prop_assert_eq!(model(set_a & set_b), &model_a & &model_b);
+            prop_assert_eq!(model(set_a - set_b), &model_a - &model_b);
+            prop_assert_eq!(model(set_a ^ set_b), &model_a ^ &model_b);
+            prop_assert_eq!(set_a.is_subset(&set_b), model_a.is_subset(&model_b));
+            prop_assert_eq!(set_a.is_disjoint(&set_b), model_a.is_disjoint(&model_b));
+        }
+    }
+}
@@ -0,0 +1,163 @@
+/// Cycle detection for simulations that repeat a step function, such as puzzles that ask for the state
+/// after a billion steps. The sequence `init, step(init), step(step(init)), ...` has to repeat eventually,
+/// otherwise the detection does not terminate.
+use std::{collections::HashMap, hash::Hash};
+
+/// The shape of an eventually periodic sequence: `start` states before the first state that repeats,
+/// followed by a cycle of `length` states.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    /// Index of the first state on the cycle, the length of the prefix.
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The smallest index whose state equals the state at `n`.
+    #[must_use]
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Brent's algorithm. Only keeps two states in memory, so states do not have to be hashable,
+/// but it calls `step` up to about three times as often as [`hashed`].
+pub fn brent<S: Clone + PartialEq>(mut step: impl FnMut(&S) -> S, init: S) -> Cycle {
+    // find the cycle length by moving the tortoise to the hare at each power of two.
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = init.clone();
+    let mut hare = step(&init);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // with the hare `length` steps ahead, both meet at the start of the cycle.
+    let mut tortoise = init.clone();
+    let mut hare = init;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Detects the cycle by remembering every state. Also returns the states up to the end of the first cycle,
+/// so that any later state can be looked up with [`Cycle::reduce`] without calling `step` again.
+pub fn hashed<S: Clone + Eq + Hash>(mut step: impl FnMut(&S) -> S, init: S) -> (Cycle, Vec<S>) {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = init;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: states.len() - start,
+            };
+            return (cycle, states);
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/// The state after `n` steps, skipping over whole cycles once the sequence repeats.
+pub fn nth_state<S: Clone + Eq + Hash>(mut step: impl FnMut(&S) -> S, init: S, n: usize) -> S {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = init;
+
+    while states.len() < n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: states.len() - start,
+            };
+            return states.swap_remove(cycle.reduce(n));
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{brent, hashed, nth_state, Cycle};
+
+    /// Starting from 3: 3, 10, then the cycle 101, 2, 5, 26, 167, 95.
+    fn step(&x: &u64) -> u64 {
+        (x * x + 1) % 255
+    }
+
+    fn naive(n: usize) -> u64 {
+        (0..n).fold(3, |x, _| step(&x))
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let expected = (0..50)
+            .flat_map(|start| (1..50).map(move |length| (start, length)))
+            .find(|&(start, length)| naive(start) == naive(start + length))
+            .map(|(start, length)| Cycle { start, length })
+            .unwrap();
+
+        assert_eq!(brent(step, 3), expected);
+        let (cycle, states) = hashed(step, 3);
+        assert_eq!(cycle, expected);
+        assert_eq!(states.len(), cycle.start + cycle.length);
+        assert!(states.iter().enumerate().all(|(i, &s)| s == naive(i)));
+    }
+
+    #[test]
+    fn detects_cycles_without_a_prefix() {
+        let rotate = |&x: &u8| (x + 1) % 5;
+        let expected = Cycle {
+            start: 0,
+            length: 5,
+        };
+        assert_eq!(brent(rotate, 2), expected);
+        assert_eq!(hashed(rotate, 2).0, expected);
+
+        let fixed = |&x: &u8| x;
+        let single = Cycle {
+            start: 0,
+            length: 1,
+        };
+        assert_eq!(brent(fixed, 1), single);
+        assert_eq!(hashed(fixed, 1).0, single);
+    }
+
+    #[test]
+    fn jumps_to_later_states() {
+        for n in 0..100 {
+            assert_eq!(nth_state(step, 3, n), naive(n), "state {n}");
+        }
+
+        let (cycle, states) = hashed(step, 3);
+        let n = 1_000_000_000;
+        assert_eq!(nth_state(step, 3, n), states[cycle.reduce(n)]);
+        assert_eq!(cycle.reduce(n), cycle.reduce(n - cycle.length));
+    }
+}
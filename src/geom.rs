@@ -0,0 +1,364 @@
+/// Points, vectors and directions for puzzles on an unbounded plane or in space.
+/// `y` grows downwards like grid rows, so that `Dir4::Up` is `(0, -1)` and points convert to and from
+/// [`Grid`] positions as `(row, col) == (y, x)`.
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::grid::{Grid, Position};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// `|a - b|`, also for unsigned types.
+fn abs_diff<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point2<T> {
+    /// Number of orthogonal steps between the points.
+    #[must_use]
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Number of steps between the points when diagonal steps are allowed.
+    #[must_use]
+    pub fn chebyshev(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+impl<T: Copy + TryFrom<usize>> Point2<T> {
+    /// The point of a grid position, or `None` if it does not fit into `T`.
+    #[must_use]
+    pub fn from_position((row, col): Position) -> Option<Self> {
+        Some(Point2 {
+            x: T::try_from(col).ok()?,
+            y: T::try_from(row).ok()?,
+        })
+    }
+}
+
+impl<T: Copy> Point2<T>
+where
+    usize: TryFrom<T>,
+{
+    /// The grid position of the point, or `None` if it lies outside of `grid`.
+    #[must_use]
+    pub fn to_position<C>(&self, grid: &Grid<C>) -> Option<Position> {
+        let pos = (usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?);
+        grid.in_bounds(pos).then_some(pos)
+    }
+}
+
+impl<T: Copy + From<i8> + Add<Output = T>> Point2<T> {
+    /// The point one step away in `dir`.
+    #[must_use]
+    pub fn step(self, dir: impl Into<Point2<T>>) -> Self {
+        self + dir.into()
+    }
+
+    /// The orthogonal neighbours, clockwise starting from up.
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Dir4::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// All neighbours including diagonals, clockwise starting from up.
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Dir8::ALL.into_iter().map(move |d| self.step(d))
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    /// Number of axis-aligned steps between the points.
+    #[must_use]
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    /// Number of steps between the points when diagonal steps are allowed.
+    #[must_use]
+    pub fn chebyshev(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x)
+            .max(abs_diff(self.y, other.y))
+            .max(abs_diff(self.z, other.z))
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident { $($field:ident),* }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $point { $($field: self.$field + other.$field),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $point { $($field: self.$field - other.$field),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $point { $($field: -self.$field),* }
+            }
+        }
+
+        /// Scales the vector by a factor.
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self {
+                $point { $($field: self.$field * factor),* }
+            }
+        }
+
+        impl<T: Copy + Add<Output = T>> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+/// An orthogonal direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// All directions, clockwise starting from up.
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    /// Rotates by `quarter_turns` quarter turns, clockwise for positive values.
+    #[must_use]
+    pub fn rotate(self, quarter_turns: i32) -> Self {
+        Self::ALL[(self as i32 + quarter_turns).rem_euclid(4) as usize]
+    }
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.rotate(-1)
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// The `(d_row, d_col)` offset for [`Grid::offset`].
+    #[must_use]
+    pub fn offset(self) -> (isize, isize) {
+        crate::grid::OFFSETS_4[self as usize]
+    }
+}
+
+/// An orthogonal or diagonal direction, named after the compass with north being up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// All directions, clockwise starting from north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    /// Rotates by `eighth_turns` steps of 45 degrees, clockwise for positive values.
+    #[must_use]
+    pub fn rotate(self, eighth_turns: i32) -> Self {
+        Self::ALL[(self as i32 + eighth_turns).rem_euclid(8) as usize]
+    }
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.rotate(-2)
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    #[must_use]
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// The `(d_row, d_col)` offset for [`Grid::offset`].
+    #[must_use]
+    pub fn offset(self) -> (isize, isize) {
+        crate::grid::OFFSETS_8[self as usize]
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Dir8::ALL[dir as usize * 2]
+    }
+}
+
+/// The unit vector of a direction.
+impl<T: From<i8>> From<Dir4> for Point2<T> {
+    fn from(dir: Dir4) -> Self {
+        Dir8::from(dir).into()
+    }
+}
+
+/// The vector of a direction, with both components set for diagonals.
+impl<T: From<i8>> From<Dir8> for Point2<T> {
+    fn from(dir: Dir8) -> Self {
+        let (d_row, d_col) = dir.offset();
+        // offsets are -1, 0 or 1.
+        Point2 {
+            x: T::from(d_col as i8),
+            y: T::from(d_row as i8),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dir4, Dir8, Point2, Point3};
+    use crate::grid::Grid;
+
+    #[test]
+    fn does_arithmetic() {
+        let (a, b) = (Point2::new(1, 2), Point2::new(4, -2));
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(a - b, Point2::new(-3, 4));
+        assert_eq!(-a * 3, Point2::new(-3, -6));
+
+        let mut c = Point3::new(1u32, 2, 3);
+        c += Point3::new(1, 1, 1);
+        c -= Point3::new(2, 0, 0);
+        assert_eq!(c, Point3::new(0, 3, 4));
+    }
+
+    #[test]
+    fn measures_distances() {
+        let (a, b) = (Point2::new(1i64, 2), Point2::new(4, -2));
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+
+        // unsigned coordinates do not underflow.
+        let (c, d) = (Point3::new(5u32, 0, 2), Point3::new(0, 3, 2));
+        assert_eq!(c.manhattan(&d), 8);
+        assert_eq!(d.chebyshev(&c), 5);
+    }
+
+    #[test]
+    fn rotates_directions() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.reverse(), Dir4::Right);
+        assert_eq!(Dir4::Down.rotate(-7), Dir4::Left);
+
+        assert_eq!(Dir8::N.rotate(1), Dir8::NE);
+        assert_eq!(Dir8::NW.turn_right(), Dir8::NE);
+        assert_eq!(Dir8::SE.reverse(), Dir8::NW);
+        assert!(Dir8::SW.is_diagonal() && !Dir8::W.is_diagonal());
+        assert_eq!(Dir8::from(Dir4::Left), Dir8::W);
+    }
+
+    #[test]
+    fn steps_in_directions() {
+        let p = Point2::new(0i32, 0);
+        assert_eq!(p.step(Dir4::Up), Point2::new(0, -1));
+        assert_eq!(p.step(Dir8::SE), Point2::new(1, 1));
+        assert_eq!(p + Point2::from(Dir4::Left) * 5, Point2::new(-5, 0));
+        assert_eq!(p.neighbours4().count(), 4);
+        assert!(p.neighbours8().all(|n| n.chebyshev(&p) == 1));
+    }
+
+    #[test]
+    fn converts_grid_positions() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+
+        let p = Point2::<i64>::from_position((1, 2)).unwrap();
+        assert_eq!(p, Point2::new(2, 1));
+        assert_eq!(p.to_position(&grid), Some((1, 2)));
+        assert_eq!(grid[p.to_position(&grid).unwrap()], 'f');
+
+        assert_eq!(p.step(Dir4::Right).to_position(&grid), None);
+        assert_eq!(Point2::new(-1i64, 0).to_position(&grid), None);
+        assert_eq!(Point2::<u8>::from_position((0, 300)), None);
+
+        // directions agree between points and grid offsets.
+        for dir in Dir8::ALL {
+            let moved = grid.offset((0, 1), dir.offset());
+            let stepped = Point2::new(1i64, 0).step(dir).to_position(&grid);
+            assert_eq!(moved, stepped, "{dir:?}");
+        }
+    }
+}
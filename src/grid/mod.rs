@@ -0,0 +1,489 @@
+/// A dense two-dimensional grid, as used by many puzzles.
+/// Positions are `(row, col)` pairs with the origin in the top-left corner.
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+pub mod tokens;
+
+pub type Position = (usize, usize);
+
+/// Offsets of the orthogonal neighbours: up, right, down, left.
+pub const OFFSETS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets of all neighbours, clockwise starting from up.
+pub const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// A row's length differs from the length of the first row.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A cell could not be converted.
+    InvalidCell { position: Position, cell: char },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} cells, expected {expected} like the first row."
+            ),
+            GridError::InvalidCell {
+                position: (row, col),
+                cell,
+            } => write!(f, "invalid cell '{cell}' at row {row}, column {col}."),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    /// Returns `None` if the number of cells is not a multiple of `width`.
+    #[must_use]
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 {
+            return cells.is_empty().then_some(Grid {
+                width,
+                height: 0,
+                cells,
+            });
+        }
+
+        cells.len().is_multiple_of(width).then(|| Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// Parses one cell per character, with one row per line.
+    /// `f` returns `None` for characters that are not valid cells.
+    pub fn parse_with(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (row, line) in input.lines().enumerate() {
+            let len_before = cells.len();
+            for (col, cell) in line.chars().enumerate() {
+                let value = f(cell).ok_or(GridError::InvalidCell {
+                    position: (row, col),
+                    cell,
+                })?;
+                cells.push(value);
+            }
+
+            let found = cells.len() - len_before;
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(GridError::Ragged {
+                    row,
+                    expected,
+                    found,
+                });
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn in_bounds(&self, (row, col): Position) -> bool {
+        row < self.height && col < self.width
+    }
+
+    fn index_of(&self, pos: Position) -> Option<usize> {
+        self.in_bounds(pos).then(|| pos.0 * self.width + pos.1)
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Moves `pos` by `(d_row, d_col)`, returning `None` if it leaves the grid.
+    #[must_use]
+    pub fn offset(&self, (row, col): Position, (d_row, d_col): (isize, isize)) -> Option<Position> {
+        let pos = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.in_bounds(pos).then_some(pos)
+    }
+
+    /// In-bounds orthogonal neighbours of `pos`.
+    pub fn neighbours4(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS_4
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// In-bounds neighbours of `pos`, including diagonals.
+    pub fn neighbours8(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    fn all_positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i / width, i % width))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.all_positions().zip(self.cells.iter())
+    }
+
+    /// Position of the first cell matching `predicate`, in row-major order.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Positions of all cells matching `predicate`, in row-major order.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Position> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "row {row} out of bounds");
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a chunk size of 0, an empty grid has no rows either way.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// # Panics
+    /// Panics if `col` is out of bounds.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} out of bounds");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Cells on the diagonal running down and to the right from `start`.
+    pub fn diagonal(&self, start: Position) -> impl Iterator<Item = &T> {
+        self.walk(start, (1, 1))
+    }
+
+    /// Cells on the diagonal running down and to the left from `start`.
+    pub fn anti_diagonal(&self, start: Position) -> impl Iterator<Item = &T> {
+        self.walk(start, (1, -1))
+    }
+
+    /// All diagonals running down and to the right, starting from the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height)
+            .rev()
+            .map(|row| (row, 0))
+            .chain((1..self.width).map(|col| (0, col)));
+        starts.map(|start| self.diagonal(start))
+    }
+
+    /// All diagonals running down and to the left, starting from the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let last_col = self.width.saturating_sub(1);
+        let starts = (0..self.width)
+            .map(|col| (0, col))
+            .chain((1..self.height).map(move |row| (row, last_col)));
+        starts.map(|start| self.anti_diagonal(start))
+    }
+
+    /// Cells from `start` onwards in direction `step`, until the edge of the grid.
+    pub fn walk(&self, start: Position, step: (isize, isize)) -> impl Iterator<Item = &T> {
+        std::iter::successors(self.get(start).map(|_| start), move |&pos| {
+            self.offset(pos, step)
+        })
+        .map(|pos| &self[pos])
+    }
+
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    #[must_use]
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        self.rebuild(self.height, self.width, |(row, col)| (col, row))
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    #[must_use]
+    pub fn rotate_cw(&self) -> Self {
+        let height = self.height;
+        self.rebuild(height, self.width, |(row, col)| (height - 1 - col, row))
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise.
+    #[must_use]
+    pub fn rotate_ccw(&self) -> Self {
+        let width = self.width;
+        self.rebuild(self.height, width, |(row, col)| (col, width - 1 - row))
+    }
+
+    /// Builds a `width` x `height` grid where each position takes the cell at `source(pos)`.
+    fn rebuild(&self, width: usize, height: usize, source: impl Fn(Position) -> Position) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|pos| self[source(pos)].clone())
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, Some)
+    }
+}
+
+impl FromStr for Grid<u8> {
+    type Err = GridError;
+
+    /// Parses a grid of single digits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, |c| c.to_digit(10).and_then(|d| u8::try_from(d).ok()))
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "position {pos:?} out of bounds for a {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} out of bounds for a {width}x{height} grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, GridError};
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    fn collect<'a>(cells: impl Iterator<Item = &'a char>) -> String {
+        cells.collect()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+    }
+
+    #[test]
+    fn parses_digit_grids() {
+        let grid: Grid<u8> = "12\n34".parse().unwrap();
+        assert_eq!(grid.row(1), &[3, 4]);
+        assert_eq!(
+            "1x".parse::<Grid<u8>>(),
+            Err(GridError::InvalidCell {
+                position: (0, 1),
+                cell: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>(),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn parses_empty_input() {
+        let grid: Grid<char> = "".parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.rows().count(), 0);
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (1, 2)), Some((1, 2)));
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = grid();
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, ["abc", "def"]);
+        let columns: Vec<String> = grid.columns().map(collect).collect();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn iterates_diagonals() {
+        let grid = grid();
+        let diagonals: Vec<String> = grid.diagonals().map(collect).collect();
+        assert_eq!(diagonals, ["d", "ae", "bf", "c"]);
+        let anti_diagonals: Vec<String> = grid.anti_diagonals().map(collect).collect();
+        assert_eq!(anti_diagonals, ["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), grid);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid: Grid<char> = "#.#\n..#".parse().unwrap();
+        assert_eq!(grid.find(|&c| c == '#'), Some((0, 0)));
+        assert_eq!(grid.find(|&c| c == 'x'), None);
+        assert_eq!(
+            grid.positions(|&c| c == '#').collect::<Vec<_>>(),
+            [(0, 0), (0, 2), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn updates_cells() {
+        let mut grid = Grid::new(2, 2, 0);
+        grid[(1, 0)] = 5;
+        *grid.get_mut((0, 1)).unwrap() += 1;
+        assert_eq!(grid.to_string(), "01\n50");
+        assert_eq!(grid.map(|v| v * 2).row(1), &[10, 0]);
+        assert_eq!(Grid::from_vec(2, vec![1, 2, 3]), None);
+    }
+}
@@ -0,0 +1,208 @@
+/// Scans a grid for tokens: horizontal runs of cells such as the numbers in a schematic.
+/// Tokens are indexed by the cells they cover, so lookups by position are constant-time.
+use std::{ops::Range, str::FromStr};
+
+use super::{Grid, Position};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Token {
+    pub row: usize,
+    /// Columns covered by the token.
+    pub cols: Range<usize>,
+    pub text: String,
+}
+
+impl Token {
+    /// Parses the token's text, e.g. as a number.
+    #[must_use]
+    pub fn value<T: FromStr>(&self) -> Option<T> {
+        self.text.parse().ok()
+    }
+
+    #[must_use]
+    pub fn contains(&self, (row, col): Position) -> bool {
+        row == self.row && self.cols.contains(&col)
+    }
+
+    /// Cells covered by the token.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.cols.clone().map(|col| (self.row, col))
+    }
+}
+
+pub struct Tokens {
+    tokens: Vec<Token>,
+    /// Index into `tokens` of the token covering each cell.
+    owners: Grid<Option<usize>>,
+}
+
+impl Tokens {
+    /// Tokens from maximal horizontal runs of cells matching `predicate`.
+    pub fn runs(grid: &Grid<char>, predicate: impl Fn(char) -> bool) -> Self {
+        Self::scan(grid, |prev, c| {
+            predicate(c) && !prev.is_some_and(&predicate)
+        })
+        .merge_runs(grid, &predicate)
+    }
+
+    /// Runs of ascii digits.
+    #[must_use]
+    pub fn numbers(grid: &Grid<char>) -> Self {
+        Self::runs(grid, |c| c.is_ascii_digit())
+    }
+
+    /// One token per cell matching `predicate`, e.g. for symbols.
+    pub fn cells(grid: &Grid<char>, predicate: impl Fn(char) -> bool) -> Self {
+        Self::scan(grid, |_, c| predicate(c))
+    }
+
+    /// Creates a single-cell token for each cell where `starts(previous cell in row, cell)` holds.
+    fn scan(grid: &Grid<char>, starts: impl Fn(Option<char>, char) -> bool) -> Self {
+        let mut tokens = vec![];
+        let mut owners = Grid::new(grid.width(), grid.height(), None);
+
+        for (row, cells) in grid.rows().enumerate() {
+            for (col, &c) in cells.iter().enumerate() {
+                let prev = col.checked_sub(1).map(|i| cells[i]);
+                if starts(prev, c) {
+                    owners[(row, col)] = Some(tokens.len());
+                    tokens.push(Token {
+                        row,
+                        cols: col..col + 1,
+                        text: c.to_string(),
+                    });
+                }
+            }
+        }
+
+        Tokens { tokens, owners }
+    }
+
+    /// Extends each token to the right while cells match `predicate`.
+    fn merge_runs(mut self, grid: &Grid<char>, predicate: impl Fn(char) -> bool) -> Self {
+        for (i, token) in self.tokens.iter_mut().enumerate() {
+            let row = grid.row(token.row);
+            while let Some(&c) = row.get(token.cols.end).filter(|&&c| predicate(c)) {
+                self.owners[(token.row, token.cols.end)] = Some(i);
+                token.text.push(c);
+                token.cols.end += 1;
+            }
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Tokens in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &Token> {
+        self.tokens.iter()
+    }
+
+    /// The token covering `pos`, if any.
+    #[must_use]
+    pub fn at(&self, pos: Position) -> Option<&Token> {
+        let index = (*self.owners.get(pos)?)?;
+        Some(&self.tokens[index])
+    }
+
+    /// Tokens covering a neighbour of `pos`, including diagonals.
+    /// A token covering `pos` itself is not included.
+    #[must_use]
+    pub fn adjacent_to(&self, pos: Position) -> Vec<&Token> {
+        let own = self.owners.get(pos).copied().flatten();
+        let mut indices: Vec<usize> = self
+            .owners
+            .neighbours8(pos)
+            .filter_map(|p| self.owners[p])
+            .filter(|&i| Some(i) != own)
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices.into_iter().map(|i| &self.tokens[i]).collect()
+    }
+
+    /// In-bounds cells bordering `token`, including diagonals.
+    pub fn border<'a>(&'a self, token: &'a Token) -> impl Iterator<Item = Position> + 'a {
+        let rows = token.row.saturating_sub(1)..=token.row + 1;
+        rows.flat_map(move |row| {
+            (token.cols.start.saturating_sub(1)..=token.cols.end).map(move |col| (row, col))
+        })
+        .filter(move |&pos| self.owners.in_bounds(pos) && !token.contains(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, Tokens};
+
+    fn grid() -> Grid<char> {
+        "12.*\n.3..\n+.45".parse().unwrap()
+    }
+
+    #[test]
+    fn scans_numbers() {
+        let grid = grid();
+        let numbers = Tokens::numbers(&grid);
+        let values: Vec<u32> = numbers.iter().filter_map(|t| t.value()).collect();
+        assert_eq!(values, [12, 3, 45]);
+
+        let last = numbers.at((2, 3)).unwrap();
+        assert_eq!((last.row, last.cols.clone()), (2, 2..4));
+        assert_eq!(numbers.at((0, 2)), None);
+    }
+
+    #[test]
+    fn scans_cells() {
+        let grid = grid();
+        let symbols = Tokens::cells(&grid, |c| c == '*' || c == '+');
+        let texts: Vec<&str> = symbols.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, ["*", "+"]);
+    }
+
+    #[test]
+    fn scans_custom_runs() {
+        let grid: Grid<char> = "..#..##".parse().unwrap();
+        let runs = Tokens::runs(&grid, |c| c == '.');
+        let spans: Vec<_> = runs.iter().map(|t| t.cols.clone()).collect();
+        assert_eq!(spans, [0..2, 3..5]);
+    }
+
+    #[test]
+    fn finds_adjacent_tokens() {
+        let grid = grid();
+        let numbers = Tokens::numbers(&grid);
+
+        let values = |pos| -> Vec<u32> {
+            numbers
+                .adjacent_to(pos)
+                .into_iter()
+                .filter_map(|t| t.value())
+                .collect()
+        };
+        assert_eq!(values((1, 2)), [12, 3, 45]);
+        assert_eq!(values((0, 3)), Vec::<u32>::new());
+        // the token covering the cell itself is not adjacent.
+        assert_eq!(values((0, 1)), [3]);
+    }
+
+    #[test]
+    fn finds_border_cells() {
+        let grid = grid();
+        let numbers = Tokens::numbers(&grid);
+
+        let first = numbers.at((0, 0)).unwrap();
+        let border: Vec<_> = numbers.border(first).collect();
+        assert_eq!(border, [(0, 2), (1, 0), (1, 1), (1, 2)]);
+
+        let middle = numbers.at((1, 1)).unwrap();
+        assert_eq!(numbers.border(middle).count(), 8);
+    }
+}
@@ -0,0 +1,105 @@
+/// Interns strings such as node or colour names into dense `u32` ids, so that solutions can index
+/// tables with `Vec`s and compare names as integers instead of hashing strings over and over.
+use std::collections::HashMap;
+
+/// Maps strings to ids `0..len` in the order they are first interned, and ids back to strings.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    ids: HashMap<String, u32>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of `name`, assigning the next free one if it is new.
+    ///
+    /// # Panics
+    /// If more than `u32::MAX` strings are interned.
+    pub fn intern(&mut self, name: &str) -> u32 {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id =
+            u32::try_from(self.names.len()).expect("interner should hold at most u32::MAX names");
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        id
+    }
+
+    /// The id of `name`, if it was interned.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    /// The string with id `id`, if there is one.
+    #[must_use]
+    pub fn resolve(&self, id: u32) -> Option<&str> {
+        self.names.get(id as usize).map(String::as_str)
+    }
+
+    /// Number of interned strings, ids are below this.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Ids and strings in order of their ids.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        (0..).zip(self.names.iter().map(String::as_str))
+    }
+}
+
+impl<'a> Extend<&'a str> for Interner {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for name in iter {
+            self.intern(name);
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Interner {
+    /// Interns the strings in order, e.g. to give known names fixed ids.
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut interner = Self::new();
+        interner.extend(iter);
+        interner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interner;
+
+    #[test]
+    fn interns_strings() {
+        let mut names = Interner::new();
+        assert_eq!(names.intern("seed"), 0);
+        assert_eq!(names.intern("soil"), 1);
+        assert_eq!(names.intern("seed"), 0);
+        assert_eq!(names.len(), 2);
+
+        assert_eq!(names.get("soil"), Some(1));
+        assert_eq!(names.get("water"), None);
+        assert_eq!(names.resolve(1), Some("soil"));
+        assert_eq!(names.resolve(2), None);
+    }
+
+    #[test]
+    fn assigns_ids_in_order() {
+        let names: Interner = ["red", "green", "blue", "red"].into_iter().collect();
+        assert_eq!(
+            names.iter().collect::<Vec<_>>(),
+            [(0, "red"), (1, "green"), (2, "blue")]
+        );
+    }
+}
@@ -0,0 +1,256 @@
+/// Sets of values stored as sorted, half-open ranges.
+/// Useful for puzzles that split and merge large ranges instead of individual values.
+use std::{iter::Sum, ops::Range, ops::Sub};
+
+/// A set stored as sorted, disjoint ranges. Touching ranges are merged, so the representation is unique.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a set from ranges that are already sorted, disjoint and not touching.
+    fn from_sorted(ranges: Vec<Range<T>>) -> Self {
+        IntervalSet { ranges }
+    }
+
+    /// Adds all values in `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // ranges[first..last] overlap or touch `range`.
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = if first < last {
+            range.start.min(self.ranges[first].start)..range.end.max(self.ranges[last - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.contains(&value))
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The smallest value in the set.
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    /// The ranges of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &Range<T>> {
+        self.ranges.iter()
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.extend(other.ranges.iter().cloned());
+        result
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = vec![];
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let range = a.start.max(b.start)..a.end.min(b.end);
+            if !range.is_empty() {
+                ranges.push(range);
+            }
+            // advance whichever range ends first, it cannot overlap anything else.
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self::from_sorted(ranges)
+    }
+
+    /// Values in `self` that are not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let mut j = 0;
+
+        for range in &self.ranges {
+            let mut start = range.start;
+            // skip ranges of `other` that end before this range.
+            while other.ranges.get(j).is_some_and(|b| b.end <= start) {
+                j += 1;
+            }
+
+            let mut k = j;
+            while let Some(b) = other.ranges.get(k).filter(|b| b.start < range.end) {
+                if start < b.start {
+                    ranges.push(start..b.start);
+                }
+                start = start.max(b.end);
+                k += 1;
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self::from_sorted(ranges)
+    }
+
+    /// Values in `bounds` that are not in the set.
+    #[must_use]
+    pub fn complement(&self, bounds: Range<T>) -> Self {
+        Self::from_iter([bounds]).difference(self)
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Sum> IntervalSet<T> {
+    /// Number of values in the set.
+    #[must_use]
+    pub fn len(&self) -> T {
+        self.ranges.iter().map(|r| r.end - r.start).sum()
+    }
+}
+
+impl<T: Copy + Ord> Extend<Range<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T> IntoIterator for IntervalSet<T> {
+    type Item = Range<T>;
+    type IntoIter = std::vec::IntoIter<Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::IntervalSet;
+    use proptest::prelude::*;
+    use std::{collections::BTreeSet, ops::Range};
+
+    const BOUNDS: Range<i32> = -50..50;
+
+    fn set(ranges: &[Range<i32>]) -> IntervalSet<i32> {
+        ranges.iter().cloned().collect()
+    }
+
+    fn model(ranges: &[Range<i32>]) -> BTreeSet<i32> {
+        ranges.iter().cloned().flatten().collect()
+    }
+
+    fn values(set: &IntervalSet<i32>) -> BTreeSet<i32> {
+        set.iter().cloned().flatten().collect()
+    }
+
+    fn is_normalized(set: &IntervalSet<i32>) -> bool {
+        let ranges: Vec<_> = set.iter().collect();
+        ranges.iter().all(|r| !r.is_empty()) && ranges.windows(2).all(|w| w[0].end < w[1].start)
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<Range<i32>>> {
+        prop::collection::vec(
+            (BOUNDS, 0..20).prop_map(|(start, len)| start..start + len),
+            0..8,
+        )
+    }
+
+    #[test]
+    fn merges_overlapping_and_touching_ranges() {
+        let set = set(&[5..8, 1..3, 3..4, 7..10, 12..12]);
+        assert_eq!(set.iter().cloned().collect::<Vec<_>>(), [1..4, 5..10]);
+        assert_eq!(set.len(), 8);
+        assert_eq!(set.min(), Some(1));
+        assert!(set.contains(9));
+        assert!(!set.contains(4));
+    }
+
+    #[test]
+    fn combines_sets() {
+        let (a, b) = (set(&[0..10, 20..30]), set(&[5..25]));
+        assert_eq!(a.union(&b), set(&[0..30]));
+        assert_eq!(a.intersection(&b), set(&[5..10, 20..25]));
+        assert_eq!(a.difference(&b), set(&[0..5, 25..30]));
+        assert_eq!(a.complement(-5..35), set(&[-5..0, 10..20, 30..35]));
+    }
+
+    proptest! {
+        #[test]
+        fn insert_matches_model(a in ranges()) {
+            let set = set(&a);
+            prop_assert!(is_normalized(&set));
+            prop_assert_eq!(values(&set), model(&a));
+            prop_assert_eq!(usize::try_from(set.len()).unwrap(), model(&a).len());
+            prop_assert_eq!(set.min(), model(&a).first().copied());
+            for value in BOUNDS.start - 20..BOUNDS.end + 20 {
+                prop_assert_eq!(set.contains(value), model(&a).contains(&value));
+            }
+        }
+
+        #[test]
+        fn set_operations_match_model(a in ranges(), b in ranges()) {
+            let (set_a, set_b) = (set(&a), set(&b));
+            let (model_a, model_b) = (model(&a), model(&b));
+
+            let union = set_a.union(&set_b);
+            prop_assert!(is_normalized(&union));
+            prop_assert_eq!(values(&union), &model_a | &model_b);
+
+            let intersection = set_a.intersection(&set_b);
+            prop_assert!(is_normalized(&intersection));
+            prop_assert_eq!(values(&intersection), &model_a & &model_b);
+
+            let difference = set_a.difference(&set_b);
+            prop_assert!(is_normalized(&difference));
+            prop_assert_eq!(values(&difference), &model_a - &model_b);
+        }
+
+        #[test]
+        fn complement_matches_model(a in ranges(), start in BOUNDS, len in 0..100) {
+            let bounds = start..start + len;
+            let complement = set(&a).complement(bounds.clone());
+            prop_assert!(is_normalized(&complement));
+            prop_assert_eq!(values(&complement), &model(&[bounds]) - &model(&a));
+        }
+    }
+}
@@ -1 +1,13 @@
+pub mod bitset;
+pub mod cycle;
+pub mod geom;
+pub mod grid;
+pub mod intern;
+pub mod interval;
+pub mod math;
+pub mod parse;
+pub mod piecewise;
+pub mod search;
 pub mod template;
+pub mod text;
+pub mod union_find;
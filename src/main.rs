@@ -1,28 +1,58 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, download, leaderboard, login, read, readme, scaffold, solve,
+};
+use advent_of_code::template::Day;
 use args::{parse, AppArguments};
 
 mod args {
+    use advent_of_code::template::{
+        commands::scaffold::{AnswerType, Days},
+        leaderboard::SortOrder,
+        Day,
+    };
     use std::process;
 
     pub enum AppArguments {
         Download {
-            day: u8,
+            day: Day,
+            force: bool,
+            wait: bool,
+            scaffold: bool,
+        },
+        Leaderboard {
+            id: u64,
+            file: Option<String>,
+            day: Option<Day>,
+            sort: SortOrder,
+        },
+        Login {
+            token: Option<String>,
+            check: bool,
         },
         Read {
-            day: u8,
+            day: Day,
+            offline: bool,
         },
+        Readme,
         Scaffold {
-            day: u8,
+            days: Days,
+            template: Option<String>,
+            type_one: AnswerType,
+            type_two: AnswerType,
+            dry_run: bool,
+            force: bool,
         },
         Solve {
-            day: u8,
+            day: Day,
             release: bool,
             time: bool,
             submit: Option<u8>,
+            check_overflow: bool,
         },
         All {
             release: bool,
             time: bool,
+            stars: bool,
         },
     }
 
@@ -33,21 +63,52 @@ mod args {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                stars: args.contains("--stars"),
             },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                force: args.contains("--force"),
+                wait: args.contains("--wait"),
+                scaffold: args.contains("--scaffold"),
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            Some("leaderboard") => AppArguments::Leaderboard {
+                id: args.free_from_str()?,
+                file: args.opt_value_from_str("--file")?,
+                day: args.opt_value_from_str("--day")?,
+                sort: args
+                    .opt_value_from_str("--sort")?
+                    .unwrap_or(SortOrder::Score),
+            },
+            Some("login") => AppArguments::Login {
+                check: args.contains("--check"),
+                token: args.opt_free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                offline: args.contains("--offline"),
             },
+            Some("readme") => AppArguments::Readme,
+            Some("scaffold") => {
+                let answer_type = args.opt_value_from_str("--type")?.unwrap_or_default();
+                AppArguments::Scaffold {
+                    template: args.opt_value_from_str("--template")?,
+                    type_one: args
+                        .opt_value_from_str("--type-one")?
+                        .unwrap_or(answer_type),
+                    type_two: args
+                        .opt_value_from_str("--type-two")?
+                        .unwrap_or(answer_type),
+                    dry_run: args.contains("--dry-run"),
+                    force: args.contains("--force"),
+                    days: args.free_from_str()?,
+                }
+            }
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                check_overflow: args.contains("--check-overflow"),
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -75,16 +136,50 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                release,
+                time,
+                stars,
+            } => all::handle(release, time, stars),
+            AppArguments::Download {
+                day,
+                force,
+                wait,
+                scaffold,
+            } => download::handle(day, force, wait, scaffold),
+            AppArguments::Leaderboard {
+                id,
+                file,
+                day,
+                sort,
+            } => leaderboard::handle(id, file, day.map(Day::into_inner), sort),
+            AppArguments::Login { token, check } => login::handle(token, check),
+            AppArguments::Read { day, offline } => read::handle(day, offline),
+            AppArguments::Readme => readme::handle(),
+            AppArguments::Scaffold {
+                days,
+                template,
+                type_one,
+                type_two,
+                dry_run,
+                force,
+            } => scaffold::handle(
+                &days.0,
+                &scaffold::Options {
+                    template: template.as_deref(),
+                    type_one,
+                    type_two,
+                    dry_run,
+                    force,
+                },
+            ),
             AppArguments::Solve {
                 day,
                 release,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                check_overflow,
+            } => solve::handle(day, release, time, submit, check_overflow),
         },
     };
 }
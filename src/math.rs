@@ -0,0 +1,428 @@
+/// Number theory and other integer math that comes up in puzzles.
+/// Helpers accept any primitive integer up to 64 bits. They compute in `i128` and return `None` if a result
+/// overflows the input type, instead of wrapping or panicking.
+use std::fmt::Debug;
+
+/// Integer types accepted by the helpers in this module.
+pub trait Int: Copy + Debug {
+    fn to_i128(self) -> i128;
+    /// Converts back, returning `None` if `value` does not fit.
+    fn from_i128(value: i128) -> Option<Self>;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {
+        $(
+            impl Int for $t {
+                fn to_i128(self) -> i128 {
+                    // lossless, all implementing types are at most 64 bits wide.
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Option<Self> {
+                    <$t>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// The greatest common divisor, which is never negative. `gcd(0, 0)` is `0`.
+/// Returns `None` only if the result does not fit, e.g. for `gcd(i64::MIN, 0)`.
+#[must_use]
+pub fn gcd<T: Int>(a: T, b: T) -> Option<T> {
+    T::from_i128(gcd_i128(a.to_i128(), b.to_i128()))
+}
+
+fn gcd_i128(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, which is never negative. It is `0` if either value is `0`.
+#[must_use]
+pub fn lcm<T: Int>(a: T, b: T) -> Option<T> {
+    T::from_i128(lcm_i128(a.to_i128(), b.to_i128())?)
+}
+
+fn lcm_i128(a: i128, b: i128) -> Option<i128> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a.abs() / gcd_i128(a, b)).checked_mul(b.abs())
+}
+
+/// The greatest common divisor of all `values`, `0` if there are none.
+pub fn gcd_all<T: Int>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    let g = values.into_iter().fold(0, |g, v| gcd_i128(g, v.to_i128()));
+    T::from_i128(g)
+}
+
+/// The least common multiple of all `values`, `1` if there are none.
+/// This is the classic "when do all cycles line up again" answer.
+pub fn lcm_all<T: Int>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    let mut l = 1;
+    for v in values {
+        l = lcm_i128(l, v.to_i128())?;
+    }
+    T::from_i128(l)
+}
+
+/// The extended Euclidean algorithm: returns `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+/// The coefficients can be negative even for unsigned inputs, so all values are `i128`.
+#[must_use]
+pub fn extended_gcd<T: Int>(a: T, b: T) -> (i128, i128, i128) {
+    let (g, x, y) = extended_gcd_i128(a.to_i128().abs(), b.to_i128().abs());
+    (g, x * sign(a.to_i128()), y * sign(b.to_i128()))
+}
+
+fn sign(value: i128) -> i128 {
+    if value < 0 {
+        -1
+    } else {
+        1
+    }
+}
+
+/// The extended Euclidean algorithm for non-negative values.
+fn extended_gcd_i128(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    (old_r, old_x, old_y)
+}
+
+/// Reduces `value` into `0..modulus`.
+fn rem(value: i128, modulus: i128) -> i128 {
+    value.rem_euclid(modulus)
+}
+
+/// `(a * b) % modulus` for values in `0..modulus`, without overflowing.
+fn mul_mod(a: i128, b: i128, modulus: i128) -> i128 {
+    // moduli come from types of at most 64 bits, so the product fits into a `u128`.
+    let product = a.unsigned_abs() * b.unsigned_abs();
+    (product % modulus.unsigned_abs()) as i128
+}
+
+/// `base.pow(exponent) % modulus`, reduced into `0..modulus`. Returns `None` if `modulus` is not positive.
+#[must_use]
+pub fn mod_pow<T: Int>(base: T, mut exponent: u64, modulus: T) -> Option<T> {
+    let modulus = modulus.to_i128();
+    if modulus <= 0 {
+        return None;
+    }
+
+    let mut base = rem(base.to_i128(), modulus);
+    let mut result = rem(1, modulus);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+
+    T::from_i128(result)
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1 (mod modulus)`.
+/// Returns `None` if `a` and `modulus` are not coprime or `modulus` is not positive.
+#[must_use]
+pub fn mod_inverse<T: Int>(a: T, modulus: T) -> Option<T> {
+    let modulus = modulus.to_i128();
+    if modulus <= 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd_i128(rem(a.to_i128(), modulus), modulus);
+    if g != 1 {
+        return None;
+    }
+    T::from_i128(rem(x, modulus))
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese remainder theorem.
+/// Returns the smallest non-negative solution and the modulus of all solutions, the lcm of the moduli.
+/// The moduli do not have to be coprime. Returns `None` if the system has no solution, a modulus is not positive,
+/// or the result does not fit.
+pub fn crt<T: Int>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    let (mut x, mut m): (i128, i128) = (0, 1);
+
+    for (residue, modulus) in congruences {
+        let (a, n) = (residue.to_i128(), modulus.to_i128());
+        if n <= 0 {
+            return None;
+        }
+
+        // x + m * k ≡ a (mod n)  <=>  (m / g) * k ≡ (a - x) / g (mod n / g)
+        let (g, p, _) = extended_gcd_i128(m, n);
+        let diff = a.checked_sub(x)?;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let n_g = n / g;
+        let k = mul_mod(rem(diff / g, n_g), rem(p, n_g), n_g);
+        let l = (m / g).checked_mul(n)?;
+        x = rem(x.checked_add(m.checked_mul(k)?)?, l);
+        m = l;
+    }
+
+    Some((T::from_i128(x)?, T::from_i128(m)?))
+}
+
+/// The integer square root, the largest `r` with `r * r <= n`. Returns `None` for negative `n`.
+#[must_use]
+pub fn isqrt<T: Int>(n: T) -> Option<T> {
+    let root = u128::try_from(n.to_i128()).ok()?.isqrt();
+    T::from_i128(i128::try_from(root).ok()?)
+}
+
+/// The next value of a sequence generated by a polynomial, using finite differences.
+/// Returns `None` for an empty sequence.
+#[must_use]
+pub fn extrapolate<T: Int>(values: &[T]) -> Option<T> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut row: Vec<i128> = values.iter().map(|v| v.to_i128()).collect();
+    let mut next: i128 = 0;
+
+    while row.iter().any(|&v| v != 0) {
+        next = next.checked_add(*row.last()?)?;
+        row = row
+            .windows(2)
+            .map(|w| w[1].checked_sub(w[0]))
+            .collect::<Option<_>>()?;
+    }
+
+    T::from_i128(next)
+}
+
+/// The value before the first one of a sequence generated by a polynomial, using finite differences.
+/// Returns `None` for an empty sequence.
+#[must_use]
+pub fn extrapolate_backwards<T: Int>(values: &[T]) -> Option<T> {
+    let reversed: Vec<T> = values.iter().rev().copied().collect();
+    extrapolate(&reversed)
+}
+
+/// Evaluates the polynomial through `points` at `x`, with Lagrange interpolation.
+/// Returns `None` if two points share an `x`, the value is not an integer, or the result does not fit.
+#[must_use]
+pub fn lagrange<T: Int>(points: &[(T, T)], x: T) -> Option<T> {
+    let points: Vec<(i128, i128)> = points
+        .iter()
+        .map(|(px, py)| (px.to_i128(), py.to_i128()))
+        .collect();
+    let x = x.to_i128();
+
+    // the sum of fractions, kept as a reduced `numerator / denominator`.
+    let (mut numerator, mut denominator): (i128, i128) = (0, 1);
+
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let (mut num, mut den): (i128, i128) = (yi, 1);
+        for (j, &(xj, _)) in points.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            if xi == xj {
+                return None;
+            }
+            num = num.checked_mul(x - xj)?;
+            den = den.checked_mul(xi - xj)?;
+            let g = gcd_i128(num, den).max(1);
+            (num, den) = (num / g, den / g);
+        }
+
+        numerator = numerator
+            .checked_mul(den)?
+            .checked_add(num.checked_mul(denominator)?)?;
+        denominator = denominator.checked_mul(den)?;
+        let g = gcd_i128(numerator, denominator).max(1);
+        (numerator, denominator) = (numerator / g, denominator / g);
+    }
+
+    if numerator % denominator != 0 {
+        return None;
+    }
+    T::from_i128(numerator / denominator)
+}
+
+/// Twice the area of a simple polygon with the shoelace formula, so that it is always an integer.
+/// The vertices can be in either order. Returns `None` if the result does not fit.
+#[must_use]
+pub fn double_area<T: Int>(vertices: &[(T, T)]) -> Option<T> {
+    let mut sum: i128 = 0;
+    for (i, &(x1, y1)) in vertices.iter().enumerate() {
+        let (x2, y2) = vertices[(i + 1) % vertices.len()];
+        let cross = x1
+            .to_i128()
+            .checked_mul(y2.to_i128())?
+            .checked_sub(x2.to_i128().checked_mul(y1.to_i128())?)?;
+        sum = sum.checked_add(cross)?;
+    }
+    T::from_i128(sum.abs())
+}
+
+/// Number of lattice points on the edges of a polygon with integer vertices.
+#[must_use]
+pub fn boundary_points<T: Int>(vertices: &[(T, T)]) -> Option<T> {
+    let mut sum: i128 = 0;
+    for (i, &(x1, y1)) in vertices.iter().enumerate() {
+        let (x2, y2) = vertices[(i + 1) % vertices.len()];
+        let dx = x2.to_i128() - x1.to_i128();
+        let dy = y2.to_i128() - y1.to_i128();
+        sum = sum.checked_add(gcd_i128(dx, dy))?;
+    }
+    T::from_i128(sum)
+}
+
+/// Number of lattice points strictly inside a polygon with integer vertices, with Pick's theorem.
+/// Adding [`boundary_points`] gives the number of cells covered by a polygon drawn through cell centres.
+#[must_use]
+pub fn interior_points<T: Int>(vertices: &[(T, T)]) -> Option<T> {
+    let area = double_area(vertices)?.to_i128();
+    let boundary = boundary_points(vertices)?.to_i128();
+    // A = I + B / 2 - 1  <=>  I = (2A - B + 2) / 2
+    T::from_i128((area - boundary + 2) / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        boundary_points, crt, double_area, extended_gcd, extrapolate, extrapolate_backwards, gcd,
+        gcd_all, interior_points, isqrt, lagrange, lcm, lcm_all, mod_inverse, mod_pow,
+    };
+    use proptest::prelude::*;
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12u32, 18), Some(6));
+        assert_eq!(gcd(-12i64, 18), Some(6));
+        assert_eq!(gcd(0u8, 0), Some(0));
+        assert_eq!(gcd(i64::MIN, 0), None);
+        assert_eq!(lcm(4u32, 6), Some(12));
+        assert_eq!(lcm(0u32, 6), Some(0));
+        assert_eq!(lcm(u32::MAX, u32::MAX - 1), None);
+        assert_eq!(gcd_all([12u64, 18, 27]), Some(3));
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(Vec::<u32>::new()), Some(1));
+    }
+
+    #[test]
+    fn computes_bezout_coefficients() {
+        for (a, b) in [(240i64, 46), (-240, 46), (240, -46), (0, 5), (7, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, i128::from(gcd(a, b).unwrap()));
+            assert_eq!(i128::from(a) * x + i128::from(b) * y, g, "{a} {b}");
+        }
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(mod_pow(4u32, 13, 497), Some(445));
+        assert_eq!(mod_pow(-2i32, 3, 5), Some(2));
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), Some(1));
+        assert_eq!(mod_pow(3u32, 0, 1), Some(0));
+        assert_eq!(mod_pow(3u32, 5, 0), None);
+
+        assert_eq!(mod_inverse(3u32, 11), Some(4));
+        assert_eq!(mod_inverse(-3i32, 11), Some(7));
+        assert_eq!(mod_inverse(6u32, 9), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt([(2u64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli do not have to be coprime.
+        assert_eq!(crt([(2u32, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1u32, 4), (2, 6)]), None);
+        assert_eq!(crt(Vec::<(u32, u32)>::new()), Some((0, 1)));
+        assert_eq!(crt([(0u8, 251), (0, 241)]), None);
+    }
+
+    #[test]
+    fn computes_integer_square_roots() {
+        assert_eq!(isqrt(0u32), Some(0));
+        assert_eq!(isqrt(15u32), Some(3));
+        assert_eq!(isqrt(16u32), Some(4));
+        assert_eq!(isqrt(u64::MAX), Some(u64::from(u32::MAX)));
+        assert_eq!(isqrt(-1i32), None);
+    }
+
+    #[test]
+    fn extrapolates_sequences() {
+        assert_eq!(extrapolate(&[0i64, 3, 6, 9, 12, 15]), Some(18));
+        assert_eq!(extrapolate(&[10i64, 13, 16, 21, 30, 45]), Some(68));
+        assert_eq!(extrapolate_backwards(&[10i64, 13, 16, 21, 30, 45]), Some(5));
+        assert_eq!(extrapolate(&[5u32]), Some(5));
+        assert_eq!(extrapolate::<u32>(&[]), None);
+        assert_eq!(extrapolate_backwards(&[1u32, 2, 3]), Some(0));
+        assert_eq!(extrapolate_backwards(&[0u32, 1, 2]), None);
+    }
+
+    #[test]
+    fn interpolates_polynomials() {
+        // y = x^2 - 3x + 1
+        let points = [(0i64, 1), (2, -1), (5, 11)];
+        assert_eq!(lagrange(&points, 10), Some(71));
+        assert_eq!(lagrange(&points, -3), Some(19));
+        assert_eq!(lagrange(&[(0u64, 0), (2, 1)], 1), None);
+        assert_eq!(lagrange(&[(1u64, 0), (1, 1)], 1), None);
+    }
+
+    #[test]
+    fn measures_polygons() {
+        let square = [(0u32, 0), (4, 0), (4, 4), (0, 4)];
+        assert_eq!(double_area(&square), Some(32));
+        assert_eq!(boundary_points(&square), Some(16));
+        assert_eq!(interior_points(&square), Some(9));
+
+        let triangle = [(0i64, 0), (-3, 0), (0, -2)];
+        assert_eq!(double_area(&triangle), Some(6));
+        assert_eq!(boundary_points(&triangle), Some(6));
+        assert_eq!(interior_points(&triangle), Some(1));
+    }
+
+    proptest! {
+        #[test]
+        fn gcd_and_lcm_divide(a in 1u32..10_000, b in 1u32..10_000) {
+            let (g, l) = (gcd(a, b).unwrap(), lcm(a, b).unwrap());
+            prop_assert_eq!(a % g + b % g, 0);
+            prop_assert_eq!(l % a + l % b, 0);
+            prop_assert_eq!(u64::from(g) * u64::from(l), u64::from(a) * u64::from(b));
+        }
+
+        #[test]
+        fn crt_matches_brute_force(
+            congruences in prop::collection::vec((0u32..20, 1u32..20), 1..4)
+        ) {
+            let congruences: Vec<(u32, u32)> =
+                congruences.into_iter().map(|(a, n)| (a % n, n)).collect();
+            let modulus = lcm_all(congruences.iter().map(|&(_, n)| n)).unwrap();
+            let brute = (0..modulus).find(|x| congruences.iter().all(|&(a, n)| x % n == a));
+            prop_assert_eq!(crt(congruences).map(|(x, _)| x), brute);
+        }
+
+        #[test]
+        fn mod_pow_matches_repeated_multiplication(base in 0u64..1000, exp in 0u64..50, m in 1u64..1000) {
+            let expected = (0..exp).fold(1 % m, |acc, _| acc * base % m);
+            prop_assert_eq!(mod_pow(base, exp, m), Some(expected));
+        }
+    }
+}
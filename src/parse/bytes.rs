@@ -0,0 +1,238 @@
+/// Fast integer parsing on byte slices, for inputs that are large or parsed many times.
+/// Unlike the helpers in [`super`], these do not report where parsing failed and panic on malformed numbers,
+/// so they are meant for hot loops over input that is known to be well-formed.
+use std::{fmt::Debug, ops::Range};
+
+/// Integer types that can be parsed from bytes. Implemented for all primitive integers up to 64 bits.
+pub trait Integer: Copy + Debug {
+    /// Whether a `-` before the digits is a sign. For unsigned types it is treated as a separator.
+    const SIGNED: bool;
+
+    /// Converts a magnitude and sign, returning `None` if the value does not fit.
+    fn from_magnitude(magnitude: u64, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($signed:literal: $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const SIGNED: bool = $signed;
+
+                fn from_magnitude(magnitude: u64, negative: bool) -> Option<Self> {
+                    let value = if negative {
+                        -i128::from(magnitude)
+                    } else {
+                        i128::from(magnitude)
+                    };
+                    <$t>::try_from(value).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(false: u8, u16, u32, u64, usize);
+impl_integer!(true: i8, i16, i32, i64, isize);
+
+/// Eight ascii digits starting at the lowest address as a number, or `None` if any byte is not a digit.
+fn parse_8_digits(chunk: [u8; 8]) -> Option<u64> {
+    let bytes = u64::from_le_bytes(chunk);
+    // every byte is in `b'0'..=b'9'` if its high nibble is 3 and adding 6 does not carry into it.
+    let is_digits = bytes & 0xF0F0_F0F0_F0F0_F0F0 == 0x3030_3030_3030_3030
+        && bytes.wrapping_add(0x0606_0606_0606_0606) & 0xF0F0_F0F0_F0F0_F0F0
+            == 0x3030_3030_3030_3030;
+    if !is_digits {
+        return None;
+    }
+
+    // combine neighbouring digits into 2, 4 and finally 8 digit numbers. The first digit is the lowest byte.
+    let mut value = bytes - 0x3030_3030_3030_3030;
+    value = (value.wrapping_mul(10) + (value >> 8)) & 0x00FF_00FF_00FF_00FF;
+    value = (value.wrapping_mul(100) + (value >> 16)) & 0x0000_FFFF_0000_FFFF;
+    value = (value.wrapping_mul(10_000) + (value >> 32)) & 0xFFFF_FFFF;
+    Some(value)
+}
+
+/// Parses `digits` as a `u64`, eight digits at a time.
+/// Returns `None` if `digits` is empty, contains anything but ascii digits, or overflows.
+#[must_use]
+pub fn parse_u64(digits: &[u8]) -> Option<u64> {
+    if digits.is_empty() {
+        return None;
+    }
+
+    let mut value: u64 = 0;
+    let mut chunks = digits.chunks_exact(8);
+    for chunk in &mut chunks {
+        let chunk = parse_8_digits(chunk.try_into().ok()?)?;
+        value = value.checked_mul(100_000_000)?.checked_add(chunk)?;
+    }
+    for &digit in chunks.remainder() {
+        if !digit.is_ascii_digit() {
+            return None;
+        }
+        value = value
+            .checked_mul(10)?
+            .checked_add(u64::from(digit - b'0'))?;
+    }
+
+    Some(value)
+}
+
+/// Parses `digits`, which may start with a `-` for signed types.
+pub(super) fn parse_integer<T: Integer>(digits: &[u8]) -> Option<T> {
+    match digits {
+        [b'-', rest @ ..] if T::SIGNED => T::from_magnitude(parse_u64(rest)?, true),
+        _ => T::from_magnitude(parse_u64(digits)?, false),
+    }
+}
+
+fn parse_or_panic<T: Integer>(digits: &[u8]) -> T {
+    parse_integer(digits).unwrap_or_else(|| {
+        panic!(
+            "{:?} is not a valid {}",
+            String::from_utf8_lossy(digits),
+            std::any::type_name::<T>()
+        )
+    })
+}
+
+/// Iterator over the integers in a byte slice, see [`ints`].
+pub struct Ints<'a, T> {
+    bytes: &'a [u8],
+    position: usize,
+    marker: std::marker::PhantomData<T>,
+}
+
+impl<T: Integer> Ints<'_, T> {
+    /// Byte range of the next integer including its sign, without parsing it.
+    pub(super) fn next_span(&mut self) -> Option<Range<usize>> {
+        let bytes = self.bytes;
+        let mut i = self.position;
+
+        loop {
+            match bytes.get(i)? {
+                b'0'..=b'9' => break,
+                b'-' if T::SIGNED && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) => break,
+                _ => i += 1,
+            }
+        }
+
+        let start = i;
+        i += 1;
+        while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        self.position = i;
+
+        Some(start..i)
+    }
+}
+
+impl<T: Integer> Iterator for Ints<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let span = self.next_span()?;
+        Some(parse_or_panic(&self.bytes[span]))
+    }
+}
+
+/// All integers in `bytes`, skipping any other bytes.
+/// For signed types, a `-` directly before digits is a sign. For unsigned types it is skipped like other bytes,
+/// so that `3-7` yields `3` and `7`.
+///
+/// # Panics
+/// When advanced to a number that does not fit into `T`.
+pub fn ints<T: Integer>(bytes: &[u8]) -> Ints<'_, T> {
+    Ints {
+        bytes,
+        position: 0,
+        marker: std::marker::PhantomData,
+    }
+}
+
+/// Splits `line` into columns of `width` bytes and parses each one, ignoring spaces.
+/// Blank columns, e.g. for missing values, are `None`. The last column may be shorter.
+///
+/// # Panics
+/// When advanced to a column that is neither blank nor a single integer that fits into `T`.
+pub fn fixed_width<T: Integer>(line: &[u8], width: usize) -> impl Iterator<Item = Option<T>> + '_ {
+    assert!(width > 0, "columns should be at least one byte wide");
+    line.chunks(width).map(|column| {
+        let start = column.iter().position(|&b| b != b' ')?;
+        let end = column.iter().rposition(|&b| b != b' ')? + 1;
+        Some(parse_or_panic(&column[start..end]))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fixed_width, ints, parse_u64};
+    use proptest::prelude::*;
+
+    #[test]
+    fn parses_u64() {
+        assert_eq!(parse_u64(b"0"), Some(0));
+        assert_eq!(parse_u64(b"1234567"), Some(1_234_567));
+        assert_eq!(parse_u64(b"12345678"), Some(12_345_678));
+        assert_eq!(parse_u64(b"0012345678901"), Some(12_345_678_901));
+        assert_eq!(parse_u64(b"18446744073709551615"), Some(u64::MAX));
+        assert_eq!(parse_u64(b"18446744073709551616"), None);
+        assert_eq!(parse_u64(b""), None);
+        assert_eq!(parse_u64(b"1234:678"), None);
+        assert_eq!(parse_u64(b"12345/78"), None);
+        assert_eq!(parse_u64(b"-1"), None);
+    }
+
+    #[test]
+    fn iterates_ints() {
+        let line = b"seeds: 79 14 55 13, x=-3..5-2";
+        assert_eq!(
+            ints::<u64>(line).collect::<Vec<_>>(),
+            [79, 14, 55, 13, 3, 5, 2]
+        );
+        assert_eq!(
+            ints::<i32>(line).collect::<Vec<_>>(),
+            [79, 14, 55, 13, -3, 5, -2]
+        );
+        assert_eq!(ints::<u8>(b"none - at all -").count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "\"300\" is not a valid u8")]
+    fn panics_on_overflow() {
+        let _ = ints::<u8>(b"1 300").collect::<Vec<_>>();
+    }
+
+    #[test]
+    fn parses_fixed_width_columns() {
+        let line = b" 41 48  6    17";
+        assert_eq!(
+            fixed_width::<u32>(line, 3).collect::<Vec<_>>(),
+            [Some(41), Some(48), Some(6), None, Some(17)]
+        );
+        assert_eq!(
+            fixed_width::<i64>(b"-1  2", 2).collect::<Vec<_>>(),
+            [Some(-1), None, Some(2)]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn matches_str_parse(value: u64, zeros in 0usize..4) {
+            let text = format!("{}{value}", "0".repeat(zeros));
+            prop_assert_eq!(parse_u64(text.as_bytes()), Some(value));
+        }
+
+        #[test]
+        fn iterates_like_str_ints(values: Vec<i64>, separator in "[ ,:a-z]{1,3}") {
+            let text = values
+                .iter()
+                .map(i64::to_string)
+                .collect::<Vec<_>>()
+                .join(&separator);
+            prop_assert_eq!(ints::<i64>(text.as_bytes()).collect::<Vec<_>>(), values);
+        }
+    }
+}
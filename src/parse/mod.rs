@@ -0,0 +1,296 @@
+/// Small, composable helpers for parsing puzzle inputs without copying.
+/// Helpers return an [`Error`] that borrows the offending part of the input. Once the whole input is known,
+/// [`Error::locate`] turns it into a [`Diagnostic`] that points at the line and column with a caret.
+use std::{fmt::Display, str::FromStr};
+
+pub mod bytes;
+
+/// A parse failure. `found` is the offending slice of the input, used to locate the failure.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error<'a> {
+    pub found: &'a str,
+    pub expected: String,
+}
+
+pub type Result<'a, T> = std::result::Result<T, Error<'a>>;
+
+impl<'a> Error<'a> {
+    pub fn new(found: &'a str, expected: impl Into<String>) -> Self {
+        Error {
+            found,
+            expected: expected.into(),
+        }
+    }
+
+    /// Locates the failure in `input`, which `found` has to be a slice of.
+    #[must_use]
+    pub fn locate(&self, input: &str) -> Diagnostic {
+        let offset = (self.found.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset + self.found.len() <= input.len());
+
+        let Some(offset) = offset else {
+            // `found` is not part of `input`, e.g. an owned string. Report it without a location.
+            return Diagnostic {
+                line: 0,
+                column: 0,
+                line_text: String::new(),
+                width: 0,
+                found: self.found.to_string(),
+                expected: self.expected.clone(),
+            };
+        };
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let line_text = input[line_start..line_end].trim_end_matches('\r');
+        // the caret should not extend beyond the line if `found` spans several lines.
+        let found = self.found.lines().next().unwrap_or_default();
+
+        Diagnostic {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            line_text: line_text.to_string(),
+            width: found.chars().count().max(1),
+            found: self.found.to_string(),
+            expected: self.expected.clone(),
+        }
+    }
+}
+
+impl Display for Error<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected {}, found {:?}", self.expected, self.found)
+    }
+}
+
+impl std::error::Error for Error<'_> {}
+
+/// A located parse failure. Prints as a caret diagnostic.
+#[derive(PartialEq, Eq)]
+pub struct Diagnostic {
+    /// 1-based line number, 0 if the location is unknown.
+    pub line: usize,
+    /// 1-based column in characters, 0 if the location is unknown.
+    pub column: usize,
+    pub line_text: String,
+    /// Number of characters to underline.
+    width: usize,
+    pub found: String,
+    pub expected: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected {}, found {:?}", self.expected, self.found)?;
+        if self.line == 0 {
+            return Ok(());
+        }
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        write!(
+            f,
+            "\n{gutter}--> line {}, column {}\n{gutter} |\n{} | {}\n{gutter} | {}{}",
+            self.line,
+            self.column,
+            self.line,
+            self.line_text,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width)
+        )
+    }
+}
+
+/// Shows the diagnostic, so that unwrapping a failed parse prints the caret.
+impl std::fmt::Debug for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+/// Parses `input` with `f`, locating a failure in `input`.
+pub fn parse<'a, T>(
+    input: &'a str,
+    f: impl FnOnce(&'a str) -> Result<'a, T>,
+) -> std::result::Result<T, Diagnostic> {
+    f(input).map_err(|e| e.locate(input))
+}
+
+/// Parses each non-empty line of `input` with `f`, locating the first failure in `input`.
+pub fn lines<'a, T>(
+    input: &'a str,
+    f: impl FnMut(&'a str) -> Result<'a, T>,
+) -> std::result::Result<Vec<T>, Diagnostic> {
+    parse(input, |input| {
+        input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(f)
+            .collect()
+    })
+}
+
+/// Splits `input` into sections separated by blank lines. Sections do not include their final newline.
+pub fn split_sections(input: &str) -> impl Iterator<Item = &str> {
+    let mut offset = 0;
+    let mut lines = input
+        .split_inclusive('\n')
+        .map(move |line| {
+            let start = offset;
+            offset += line.len();
+            (start, line.trim_end_matches(['\n', '\r']))
+        })
+        .peekable();
+
+    std::iter::from_fn(move || {
+        let (start, first) = lines.find(|(_, line)| !line.trim().is_empty())?;
+        let mut end = start + first.len();
+        while let Some((line_start, line)) = lines.next_if(|(_, line)| !line.trim().is_empty()) {
+            end = line_start + line.len();
+        }
+        Some(&input[start..end])
+    })
+}
+
+/// Strips `tag` from the start of `s`.
+pub fn tag<'a>(s: &'a str, tag: &str) -> Result<'a, &'a str> {
+    s.strip_prefix(tag)
+        .ok_or_else(|| Error::new(first_word(s), format!("{tag:?}")))
+}
+
+/// Splits the longest prefix of `s` whose characters match `predicate` from the rest.
+pub fn prefix(s: &str, predicate: impl Fn(char) -> bool) -> (&str, &str) {
+    let end = s.find(|c| !predicate(c)).unwrap_or(s.len());
+    s.split_at(end)
+}
+
+/// Splits `s` at the first occurrence of `delimiter`.
+pub fn split_once<'a>(s: &'a str, delimiter: &str) -> Result<'a, (&'a str, &'a str)> {
+    s.split_once(delimiter)
+        .ok_or_else(|| Error::new(s, format!("{delimiter:?}")))
+}
+
+/// Parses `s` as an integer, ignoring surrounding whitespace.
+pub fn int<T: FromStr>(s: &str) -> Result<'_, T> {
+    let trimmed = s.trim();
+    trimmed
+        .parse()
+        .map_err(|_| Error::new(if trimmed.is_empty() { s } else { trimmed }, "an integer"))
+}
+
+/// Parses all integers in `s`, skipping any other characters. For signed types, a `-` directly before digits
+/// is a sign. For unsigned types it is skipped like other characters, so that a range `3-7` yields `3` and `7`.
+/// Scans like [`bytes::ints`], but reports numbers that do not fit into `T` instead of panicking.
+pub fn ints<T: bytes::Integer>(s: &str) -> Result<'_, Vec<T>> {
+    let mut numbers = bytes::ints::<T>(s.as_bytes());
+    let mut values = vec![];
+
+    while let Some(span) = numbers.next_span() {
+        let number = &s[span];
+        values.push(
+            bytes::parse_integer(number.as_bytes())
+                .ok_or_else(|| Error::new(number, "an integer"))?,
+        );
+    }
+
+    Ok(values)
+}
+
+/// Parses a list of `key<separator>value` items separated by `delimiter`, e.g. `a: 1, b: 2`.
+/// Keys and values are trimmed.
+pub fn key_values<'a>(
+    s: &'a str,
+    delimiter: &str,
+    separator: &str,
+) -> Result<'a, Vec<(&'a str, &'a str)>> {
+    s.split(delimiter)
+        .map(|item| {
+            let (key, value) = split_once(item.trim(), separator)?;
+            Ok((key.trim(), value.trim()))
+        })
+        .collect()
+}
+
+/// The first whitespace-separated word of `s`, or `s` itself if it is blank.
+fn first_word(s: &str) -> &str {
+    s.split_whitespace().next().unwrap_or(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{int, ints, key_values, lines, parse, prefix, split_once, split_sections, tag};
+
+    #[test]
+    fn parses_ints() {
+        assert_eq!(ints::<i32>("x=3, y=-14 to 5-2"), Ok(vec![3, -14, 5, -2]));
+        assert_eq!(ints::<u32>("none"), Ok(vec![]));
+        assert_eq!(ints::<u32>("3-7"), Ok(vec![3, 7]));
+        assert_eq!(ints::<u64>("1-3 a"), Ok(vec![1, 3]));
+        assert!(ints::<u8>("300").is_err());
+        assert_eq!(int::<u32>(" 42 "), Ok(42));
+        assert!(int::<u32>("4x").is_err());
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "a\nb\n\nc\n \n\n\nd\n";
+        assert_eq!(
+            split_sections(input).collect::<Vec<_>>(),
+            ["a\nb", "c", "d"]
+        );
+        assert_eq!(split_sections("a\r\n\r\nb").collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(split_sections("").count(), 0);
+    }
+
+    #[test]
+    fn matches_tags_and_prefixes() {
+        assert_eq!(tag("Game 1", "Game "), Ok("1"));
+        assert!(tag("Card 1", "Game ").is_err());
+        assert_eq!(prefix("abc123", char::is_alphabetic), ("abc", "123"));
+        assert_eq!(split_once("a: b", ": "), Ok(("a", "b")));
+    }
+
+    #[test]
+    fn parses_key_values() {
+        assert_eq!(
+            key_values("3 blue, 4 red", ",", " "),
+            Ok(vec![("3", "blue"), ("4", "red")])
+        );
+        assert!(key_values("3 blue, red", ",", " ").is_err());
+    }
+
+    #[test]
+    fn locates_errors() {
+        let input = "Game 1: 3\nGame 2: x4\n";
+        let err = lines(input, |line| {
+            let (_, value) = split_once(line, ": ")?;
+            int::<u32>(value)
+        })
+        .unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 9));
+        assert_eq!(err.line_text, "Game 2: x4");
+        assert_eq!(
+            err.to_string(),
+            "expected an integer, found \"x4\"\n --> line 2, column 9\n  |\n2 | Game 2: x4\n  |         ^^"
+        );
+    }
+
+    #[test]
+    fn locates_errors_at_the_end_of_a_line() {
+        let err = parse("Game", |s| tag(s, "Game ")).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+
+        let err = parse("a\nb:", |s| {
+            let (_, value) = split_once(s.lines().nth(1).unwrap(), ":")?;
+            int::<u32>(value)
+        })
+        .unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert!(err.to_string().ends_with("\n2 | b:\n  |   ^"));
+    }
+}
@@ -0,0 +1,387 @@
+/// Piecewise-linear integer maps, as used by puzzles that chain "dest src len" range mappings.
+/// Each piece shifts the values in its range by a constant offset, values outside all pieces map to themselves.
+use std::{fmt::Display, ops::Range, str::FromStr};
+
+/// A map over all `i64` values, stored as sorted breakpoints.
+/// The first piece starts at `i64::MIN` and each piece extends up to the start of the next one.
+/// Every value maps to an `i64` again and `i64::MAX` always maps to itself, but the offset between a value
+/// and its image may not fit into an `i64`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    pieces: Vec<Piece>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Piece {
+    start: i64,
+    offset: i128,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MapError {
+    /// A line is not made of three integers `dest src len`.
+    InvalidLine(String),
+    /// Two source ranges overlap.
+    Overlap(Range<i64>, Range<i64>),
+    /// The source or destination range of a `dest src len` triple ends past `i64::MAX`.
+    Overflow(i64, i64, i64),
+}
+
+impl Display for MapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MapError::InvalidLine(line) => {
+                write!(f, "invalid line \"{line}\", expected \"dest src len\".")
+            }
+            MapError::Overlap(a, b) => write!(f, "source ranges {a:?} and {b:?} overlap."),
+            MapError::Overflow(dest, src, len) => {
+                write!(
+                    f,
+                    "the ranges of \"{dest} {src} {len}\" end past {}.",
+                    i64::MAX
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+/// One past the largest `i64`, the exclusive end of the last piece.
+const END: i128 = i64::MAX as i128 + 1;
+
+/// Converts a bound or image back. These always fit, as only the last piece ends at [`END`] and it maps
+/// `i64::MAX` to itself.
+fn to_i64(value: i128) -> i64 {
+    i64::try_from(value).expect("images of i64 values should be i64 values")
+}
+
+impl PiecewiseMap {
+    /// Maps every value to itself.
+    #[must_use]
+    pub fn identity() -> Self {
+        PiecewiseMap {
+            pieces: vec![Piece {
+                start: i64::MIN,
+                offset: 0,
+            }],
+        }
+    }
+
+    /// Builds a map from `(dest, src, len)` triples, mapping `src..src + len` to `dest..dest + len`.
+    /// Fails if source ranges overlap or a range does not fit into `i64`.
+    pub fn from_triples(
+        triples: impl IntoIterator<Item = (i64, i64, i64)>,
+    ) -> Result<Self, MapError> {
+        let mut ranges: Vec<(Range<i64>, i128)> = triples
+            .into_iter()
+            .filter(|&(_, _, len)| len > 0)
+            .map(|(dest, src, len)| {
+                let src_end = src.checked_add(len);
+                let dest_end = dest.checked_add(len);
+                match (src_end, dest_end) {
+                    (Some(src_end), Some(_)) => {
+                        Ok((src..src_end, i128::from(dest) - i128::from(src)))
+                    }
+                    _ => Err(MapError::Overflow(dest, src, len)),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        ranges.sort_by_key(|(range, _)| range.start);
+
+        if let Some(pair) = ranges.windows(2).find(|w| w[0].0.end > w[1].0.start) {
+            return Err(MapError::Overlap(pair[0].0.clone(), pair[1].0.clone()));
+        }
+
+        let mut pieces = vec![];
+        for (range, offset) in ranges {
+            pieces.push(Piece {
+                start: range.start,
+                offset,
+            });
+            pieces.push(Piece {
+                start: range.end,
+                offset: 0,
+            });
+        }
+
+        Ok(Self::from_pieces(pieces))
+    }
+
+    /// Sorts `pieces`, fills the start of the domain and merges neighbours with equal offsets.
+    /// For pieces with equal starts, the one pushed last wins.
+    fn from_pieces(mut pieces: Vec<Piece>) -> Self {
+        pieces.insert(
+            0,
+            Piece {
+                start: i64::MIN,
+                offset: 0,
+            },
+        );
+        pieces.sort_by_key(|p| p.start);
+
+        let mut merged: Vec<Piece> = vec![];
+        for piece in pieces {
+            match merged.last_mut() {
+                Some(last) if last.start == piece.start => *last = piece,
+                _ => merged.push(piece),
+            }
+        }
+        merged.dedup_by(|next, prev| next.offset == prev.offset);
+
+        PiecewiseMap { pieces: merged }
+    }
+
+    /// Pieces with their ranges as `i128`, so that the last end is representable.
+    fn ranges(&self) -> impl Iterator<Item = (Range<i128>, i128)> + '_ {
+        let ends = self
+            .pieces
+            .iter()
+            .skip(1)
+            .map(|p| i128::from(p.start))
+            .chain([END]);
+
+        self.pieces
+            .iter()
+            .zip(ends)
+            .map(|(p, end)| (i128::from(p.start)..end, p.offset))
+    }
+
+    /// Ranges that are not mapped to themselves, with their offsets.
+    pub fn segments(&self) -> impl Iterator<Item = (Range<i64>, i128)> + '_ {
+        self.ranges()
+            .filter(|(_, offset)| *offset != 0)
+            .map(|(r, offset)| (to_i64(r.start)..to_i64(r.end), offset))
+    }
+
+    fn piece_at(&self, value: i64) -> &Piece {
+        // the first piece starts at `i64::MIN`, so the partition point is at least 1.
+        &self.pieces[self.pieces.partition_point(|p| p.start <= value) - 1]
+    }
+
+    /// Maps a single value in `O(log n)`.
+    #[must_use]
+    pub fn get(&self, value: i64) -> i64 {
+        to_i64(i128::from(value) + self.piece_at(value).offset)
+    }
+
+    /// Maps all values in `range`. The result holds one range per piece that `range` overlaps.
+    #[must_use]
+    pub fn map_range(&self, range: Range<i64>) -> Vec<Range<i64>> {
+        let (start, end) = (i128::from(range.start), i128::from(range.end));
+
+        self.ranges()
+            .filter_map(|(piece, offset)| {
+                let lo = start.max(piece.start);
+                let hi = end.min(piece.end);
+                (lo < hi).then(|| to_i64(lo + offset)..to_i64(hi + offset))
+            })
+            .collect()
+    }
+
+    /// Composes two maps into one that applies `self`, then `next`.
+    #[must_use]
+    pub fn then(&self, next: &Self) -> Self {
+        let mut pieces = vec![];
+
+        for (range, offset) in self.ranges() {
+            // split the image of this piece at the breakpoints of `next`.
+            let image = range.start + offset..range.end + offset;
+            for (next_range, next_offset) in next.ranges() {
+                let lo = image.start.max(next_range.start);
+                if lo < image.end.min(next_range.end) {
+                    pieces.push(Piece {
+                        start: to_i64(lo - offset),
+                        offset: offset + next_offset,
+                    });
+                }
+            }
+        }
+
+        Self::from_pieces(pieces)
+    }
+
+    /// The inverse map, or `None` if the map is not a bijection.
+    #[must_use]
+    pub fn invert(&self) -> Option<Self> {
+        let mut images: Vec<(Range<i128>, i128)> = self
+            .ranges()
+            .map(|(range, offset)| (range.start + offset..range.end + offset, -offset))
+            .collect();
+        images.sort_by_key(|(range, _)| range.start);
+
+        // the images have to tile the whole domain without gaps or overlaps.
+        let mut expected = i128::from(i64::MIN);
+        for (range, _) in &images {
+            if range.start != expected {
+                return None;
+            }
+            expected = range.end;
+        }
+        if expected != END {
+            return None;
+        }
+
+        let pieces = images
+            .into_iter()
+            .map(|(range, offset)| Piece {
+                start: to_i64(range.start),
+                offset,
+            })
+            .collect();
+
+        Some(Self::from_pieces(pieces))
+    }
+}
+
+impl Default for PiecewiseMap {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl FromStr for PiecewiseMap {
+    type Err = MapError;
+
+    /// Parses one `dest src len` triple per line.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let triples = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let numbers: Vec<i64> = line
+                    .split_whitespace()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| MapError::InvalidLine(line.into()))?;
+
+                match numbers[..] {
+                    [dest, src, len] => Ok((dest, src, len)),
+                    _ => Err(MapError::InvalidLine(line.into())),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_triples(triples)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::{MapError, PiecewiseMap};
+
+    fn seed_to_soil() -> PiecewiseMap {
+        "50 98 2\n52 50 48".parse().unwrap()
+    }
+
+    fn soil_to_fertilizer() -> PiecewiseMap {
+        "0 15 37\n37 52 2\n39 0 15".parse().unwrap()
+    }
+
+    #[test]
+    fn looks_up_values() {
+        let map = seed_to_soil();
+        assert_eq!(map.get(1), 1);
+        assert_eq!(map.get(50), 52);
+        assert_eq!(map.get(97), 99);
+        assert_eq!(map.get(98), 50);
+        assert_eq!(map.get(99), 51);
+        assert_eq!(map.get(100), 100);
+        assert_eq!(map.get(i64::MIN), i64::MIN);
+        assert_eq!(map.get(i64::MAX), i64::MAX);
+        assert_eq!(map.segments().count(), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert_eq!(
+            "1 2".parse::<PiecewiseMap>(),
+            Err(MapError::InvalidLine("1 2".into()))
+        );
+        assert_eq!(
+            "0 10 5\n20 12 5".parse::<PiecewiseMap>(),
+            Err(MapError::Overlap(10..15, 12..17))
+        );
+    }
+
+    #[test]
+    fn rejects_ranges_past_i64_max() {
+        assert_eq!(
+            "0 9223372036854775800 100".parse::<PiecewiseMap>(),
+            Err(MapError::Overflow(0, 9_223_372_036_854_775_800, 100))
+        );
+        assert_eq!(
+            "9223372036854775800 0 100".parse::<PiecewiseMap>(),
+            Err(MapError::Overflow(9_223_372_036_854_775_800, 0, 100))
+        );
+    }
+
+    #[test]
+    fn handles_offsets_beyond_i64() {
+        // shifts the lowest values to the top and back, offsets that do not fit into an `i64`.
+        let up: PiecewiseMap = "9223372036854775000 -9223372036854775808 10"
+            .parse()
+            .unwrap();
+        let down: PiecewiseMap = "-9223372036854775808 9223372036854775000 10"
+            .parse()
+            .unwrap();
+
+        assert_eq!(up.get(i64::MIN + 3), 9_223_372_036_854_775_003);
+        assert_eq!(down.get(9_223_372_036_854_775_009), i64::MIN + 9);
+        assert_eq!(up.get(i64::MAX), i64::MAX);
+        assert_eq!(
+            up.map_range(i64::MIN..i64::MIN + 20),
+            [
+                9_223_372_036_854_775_000..9_223_372_036_854_775_010,
+                i64::MIN + 10..i64::MIN + 20
+            ]
+        );
+        let round_trip = up.then(&down);
+        assert_eq!(round_trip.get(i64::MIN + 3), i64::MIN + 3);
+        assert_eq!(round_trip.get(9_223_372_036_854_775_003), i64::MIN + 3);
+        assert_eq!(
+            up.segments().next(),
+            Some((i64::MIN..i64::MIN + 10, (1 << 64) - 808))
+        );
+    }
+
+    #[test]
+    fn maps_ranges() {
+        let map = seed_to_soil();
+        assert_eq!(map.map_range(45..100), [45..50, 52..100, 50..52]);
+        assert_eq!(map.map_range(60..61), [62..63]);
+        assert_eq!(map.map_range(5..5), []);
+    }
+
+    #[test]
+    fn composes_maps() {
+        let (first, second) = (seed_to_soil(), soil_to_fertilizer());
+        let composed = first.then(&second);
+
+        for value in -10..120 {
+            assert_eq!(composed.get(value), second.get(first.get(value)));
+        }
+        assert_eq!(
+            PiecewiseMap::identity().then(&first),
+            first.then(&PiecewiseMap::identity())
+        );
+    }
+
+    #[test]
+    fn inverts_bijections() {
+        let map = seed_to_soil();
+        let inverse = map.invert().unwrap();
+
+        for value in -10..120 {
+            assert_eq!(inverse.get(map.get(value)), value);
+        }
+        assert_eq!(map.then(&inverse), PiecewiseMap::identity());
+    }
+
+    #[test]
+    fn does_not_invert_non_bijections() {
+        let map: PiecewiseMap = "0 10 5".parse().unwrap();
+        assert_eq!(map.invert(), None);
+    }
+}
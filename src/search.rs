@@ -0,0 +1,556 @@
+/// Graph searches over any state type, with the graph given as a successor closure.
+/// Searches call a visitor for each state they visit, which can stop the search at a goal or skip a state,
+/// and return the [`Reached`] states so that costs and paths can be looked up afterwards.
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use crate::grid::{Grid, Position};
+
+/// What a search does after visiting a state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Visit {
+    /// Expands the state and carries on.
+    Continue,
+    /// Does not expand the state, but carries on with other states.
+    Skip,
+    /// Stops the search, making this state the goal.
+    Stop,
+}
+
+/// A visitor that stops at the first state matching `is_goal`.
+pub fn until<S, C>(mut is_goal: impl FnMut(&S) -> bool) -> impl FnMut(&S, C) -> Visit {
+    move |state, _| {
+        if is_goal(state) {
+            Visit::Stop
+        } else {
+            Visit::Continue
+        }
+    }
+}
+
+/// A visitor that visits every reachable state.
+pub fn exhaust<S, C>(_: &S, _: C) -> Visit {
+    Visit::Continue
+}
+
+struct Node<S, C> {
+    state: S,
+    cost: C,
+    parent: Option<usize>,
+}
+
+/// States reached by a search, with their costs and the states they were reached from.
+pub struct Reached<S, C> {
+    nodes: Vec<Node<S, C>>,
+    index: HashMap<S, usize>,
+    goal: Option<usize>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Reached<S, C> {
+    fn new() -> Self {
+        Reached {
+            nodes: vec![],
+            index: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    fn insert(&mut self, state: S, cost: C, parent: Option<usize>) -> usize {
+        let i = self.nodes.len();
+        self.index.insert(state.clone(), i);
+        self.nodes.push(Node {
+            state,
+            cost,
+            parent,
+        });
+        i
+    }
+
+    /// Number of reached states.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, state: &S) -> bool {
+        self.index.contains_key(state)
+    }
+
+    /// The cost of reaching `state`, e.g. its distance from the start for a breadth-first search.
+    #[must_use]
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.index.get(state).map(|&i| self.nodes[i].cost)
+    }
+
+    /// The state the visitor stopped the search at.
+    #[must_use]
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.map(|i| &self.nodes[i].state)
+    }
+
+    #[must_use]
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal.map(|i| self.nodes[i].cost)
+    }
+
+    /// The path from the start to `state`, including both ends.
+    #[must_use]
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.index.get(state).map(|&i| self.path_from_index(i))
+    }
+
+    /// The path from the start to the goal, including both ends.
+    #[must_use]
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.goal.map(|i| self.path_from_index(i))
+    }
+
+    fn path_from_index(&self, mut i: usize) -> Vec<S> {
+        let mut path = vec![self.nodes[i].state.clone()];
+        while let Some(parent) = self.nodes[i].parent {
+            path.push(self.nodes[parent].state.clone());
+            i = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Reached states with their costs, in the order they were reached.
+    pub fn iter(&self) -> impl Iterator<Item = (&S, C)> {
+        self.nodes.iter().map(|n| (&n.state, n.cost))
+    }
+}
+
+/// Breadth-first search from `start`. Costs are the number of steps from the start.
+/// States are visited in order of their distance, so the first goal found is a closest one.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut visit: impl FnMut(&S, usize) -> Visit,
+) -> Reached<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut reached = Reached::new();
+    let mut queue = VecDeque::from([reached.insert(start, 0, None)]);
+
+    while let Some(i) = queue.pop_front() {
+        let (state, cost) = (&reached.nodes[i].state, reached.nodes[i].cost);
+        match visit(state, cost) {
+            Visit::Continue => {}
+            Visit::Skip => continue,
+            Visit::Stop => {
+                reached.goal = Some(i);
+                break;
+            }
+        }
+
+        for next in successors(state) {
+            if !reached.contains(&next) {
+                queue.push_back(reached.insert(next, cost + 1, Some(i)));
+            }
+        }
+    }
+
+    reached
+}
+
+/// Depth-first search from `start`. Costs are depths in the search tree, not shortest distances.
+/// Successors are visited in the order they are returned.
+pub fn dfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut visit: impl FnMut(&S, usize) -> Visit,
+) -> Reached<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut reached = Reached::new();
+    let mut stack: Vec<(S, usize, Option<usize>)> = vec![(start, 0, None)];
+
+    while let Some((state, cost, parent)) = stack.pop() {
+        if reached.contains(&state) {
+            continue;
+        }
+        let i = reached.insert(state, cost, parent);
+        let state = &reached.nodes[i].state;
+
+        match visit(state, cost) {
+            Visit::Continue => {}
+            Visit::Skip => continue,
+            Visit::Stop => {
+                reached.goal = Some(i);
+                break;
+            }
+        }
+
+        let successors: Vec<S> = successors(state).into_iter().collect();
+        // push in reverse, so that the first successor is popped first.
+        for next in successors.into_iter().rev() {
+            if !reached.contains(&next) {
+                stack.push((next, cost + 1, Some(i)));
+            }
+        }
+    }
+
+    reached
+}
+
+/// Dijkstra's algorithm from `start`. Successors come with the cost of the step to them, which must not be negative.
+/// Only visited states are reached, and their costs are the lowest ones.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    visit: impl FnMut(&S, C) -> Visit,
+) -> Reached<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), visit)
+}
+
+/// A* search from `start`, guided by `heuristic`, an estimate of the remaining cost to a goal.
+/// The heuristic must be consistent: it never decreases by more than the cost of a step, and is zero at goals.
+/// Otherwise the costs of visited states may not be the lowest ones.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut visit: impl FnMut(&S, C) -> Visit,
+) -> Reached<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut reached = Reached::new();
+    // candidates for the next visit, with the lowest cost found for each state so far.
+    let mut pending: Vec<(S, Option<usize>)> = vec![];
+    let mut best: HashMap<S, C> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    let cost = C::default();
+    heap.push(Reverse((heuristic(&start), cost, 0)));
+    best.insert(start.clone(), cost);
+    pending.push((start, None));
+
+    while let Some(Reverse((_, cost, p))) = heap.pop() {
+        let (state, parent) = &pending[p];
+        if reached.contains(state) || best.get(state).is_some_and(|&b| b < cost) {
+            continue;
+        }
+        let i = reached.insert(state.clone(), cost, *parent);
+        let state = &reached.nodes[i].state;
+
+        match visit(state, cost) {
+            Visit::Continue => {}
+            Visit::Skip => continue,
+            Visit::Stop => {
+                reached.goal = Some(i);
+                break;
+            }
+        }
+
+        for (next, step) in successors(state) {
+            let next_cost = cost + step;
+            if reached.contains(&next) || best.get(&next).is_some_and(|&b| b <= next_cost) {
+                continue;
+            }
+            best.insert(next.clone(), next_cost);
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                pending.len(),
+            )));
+            pending.push((next, Some(i)));
+        }
+    }
+
+    reached
+}
+
+/// A shortest path from `start` to `goal`, searching forwards from the start and backwards from the goal.
+/// `predecessors` returns the states with a step to the given one, for undirected graphs it is `successors` again.
+/// Explores far fewer states than [`bfs`] when the graph branches a lot.
+pub fn bidirectional_bfs<S, I, J>(
+    start: S,
+    goal: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut predecessors: impl FnMut(&S) -> J,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+    J: IntoIterator<Item = S>,
+{
+    if start == goal {
+        return Some(vec![start]);
+    }
+
+    // distance from the start or goal, and the state each state was reached from.
+    let mut forward: HashMap<S, (usize, Option<S>)> = HashMap::from([(start.clone(), (0, None))]);
+    let mut backward: HashMap<S, (usize, Option<S>)> = HashMap::from([(goal.clone(), (0, None))]);
+    let (mut forward_layer, mut backward_layer) = (vec![start], vec![goal]);
+
+    while !forward_layer.is_empty() && !backward_layer.is_empty() {
+        // expand the smaller side by a whole layer. The frontiers are disjoint before,
+        // so the shortest path meets in this layer at a state closest to the other end.
+        let expand_forward = forward_layer.len() <= backward_layer.len();
+        let (layer, this, other) = if expand_forward {
+            (&mut forward_layer, &mut forward, &backward)
+        } else {
+            (&mut backward_layer, &mut backward, &forward)
+        };
+
+        let mut next_layer = vec![];
+        let mut meeting: Option<(usize, S)> = None;
+
+        for state in layer.drain(..) {
+            let distance = this[&state].0;
+            let neighbours: Vec<S> = if expand_forward {
+                successors(&state).into_iter().collect()
+            } else {
+                predecessors(&state).into_iter().collect()
+            };
+
+            for next in neighbours {
+                if this.contains_key(&next) {
+                    continue;
+                }
+                if let Some(&(rest, _)) = other.get(&next) {
+                    if meeting
+                        .as_ref()
+                        .is_none_or(|(total, _)| distance + 1 + rest < *total)
+                    {
+                        meeting = Some((distance + 1 + rest, next.clone()));
+                    }
+                }
+                this.insert(next.clone(), (distance + 1, Some(state.clone())));
+                next_layer.push(next);
+            }
+        }
+
+        if let Some((_, middle)) = meeting {
+            let mut path = trace(&forward, &middle);
+            path.reverse();
+            path.extend(trace(&backward, &middle).into_iter().skip(1));
+            return Some(path);
+        }
+        *layer = next_layer;
+    }
+
+    None
+}
+
+/// Follows parents from `state` back to the end of the search.
+fn trace<S: Clone + Eq + Hash>(parents: &HashMap<S, (usize, Option<S>)>, state: &S) -> Vec<S> {
+    let mut path = vec![state.clone()];
+    while let Some((_, Some(parent))) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path
+}
+
+/// Successors for searches on a grid: the orthogonal neighbours that `passable(from, to)` allows.
+pub fn grid4<'a, T>(
+    grid: &'a Grid<T>,
+    passable: impl Fn(Position, Position) -> bool + 'a,
+) -> impl Fn(&Position) -> Vec<Position> + 'a {
+    move |&pos| {
+        grid.neighbours4(pos)
+            .filter(|&next| passable(pos, next))
+            .collect()
+    }
+}
+
+/// Successors for searches on a grid: all neighbours including diagonals that `passable(from, to)` allows.
+pub fn grid8<'a, T>(
+    grid: &'a Grid<T>,
+    passable: impl Fn(Position, Position) -> bool + 'a,
+) -> impl Fn(&Position) -> Vec<Position> + 'a {
+    move |&pos| {
+        grid.neighbours8(pos)
+            .filter(|&next| passable(pos, next))
+            .collect()
+    }
+}
+
+/// Weighted successors for [`dijkstra`] and [`astar`] on a grid: the orthogonal neighbours with the cost of stepping
+/// to them, or `None` if they cannot be entered.
+pub fn weighted_grid4<'a, T, C>(
+    grid: &'a Grid<T>,
+    cost: impl Fn(Position, Position) -> Option<C> + 'a,
+) -> impl Fn(&Position) -> Vec<(Position, C)> + 'a {
+    move |&pos| {
+        grid.neighbours4(pos)
+            .filter_map(|next| Some((next, cost(pos, next)?)))
+            .collect()
+    }
+}
+
+/// Positions that can be reached on a grid, e.g. to flood-fill a region.
+pub fn flood_fill<T>(
+    grid: &Grid<T>,
+    start: Position,
+    passable: impl Fn(Position, Position) -> bool,
+) -> HashSet<Position> {
+    bfs(start, grid4(grid, passable), exhaust)
+        .iter()
+        .map(|(&pos, _)| pos)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        astar, bfs, bidirectional_bfs, dfs, dijkstra, exhaust, flood_fill, grid4, grid8, until,
+        weighted_grid4, Visit,
+    };
+    use crate::grid::Grid;
+
+    fn maze() -> Grid<char> {
+        "S.#.....\n.##.###.\n...#...E\n.#...#..".parse().unwrap()
+    }
+
+    fn open(grid: &Grid<char>) -> impl Fn((usize, usize), (usize, usize)) -> bool + '_ {
+        |_, to| grid[to] != '#'
+    }
+
+    /// Steps on a bounded number line that add one or double.
+    fn steps(&n: &u32) -> Vec<u32> {
+        [n + 1, n * 2].into_iter().filter(|&m| m <= 256).collect()
+    }
+
+    #[test]
+    fn finds_shortest_paths_with_bfs() {
+        let grid = maze();
+        let (start, end) = (
+            grid.find(|&c| c == 'S').unwrap(),
+            grid.find(|&c| c == 'E').unwrap(),
+        );
+
+        let reached = bfs(start, grid4(&grid, open(&grid)), until(|&pos| pos == end));
+        assert_eq!(reached.goal(), Some(&end));
+        assert_eq!(reached.goal_cost(), Some(11));
+
+        let path = reached.path().unwrap();
+        assert_eq!(path.len(), 12);
+        assert_eq!((path[0], path[11]), (start, end));
+        assert!(path
+            .windows(2)
+            .all(|w| grid.neighbours4(w[0]).any(|p| p == w[1])));
+        assert!(path.iter().all(|&p| grid[p] != '#'));
+    }
+
+    #[test]
+    fn visits_every_state_without_a_goal() {
+        let grid = maze();
+        let reached = bfs((0, 0), grid4(&grid, open(&grid)), exhaust);
+        assert_eq!(
+            reached.len(),
+            grid.iter().filter(|(_, &c)| c != '#').count()
+        );
+        assert_eq!(reached.goal(), None);
+        assert_eq!(reached.cost(&(2, 7)), Some(11));
+        assert_eq!(reached.cost(&(0, 2)), None);
+
+        let diagonal = bfs((0, 0), grid8(&grid, open(&grid)), exhaust);
+        assert!(diagonal.cost(&(2, 7)).unwrap() < 11);
+    }
+
+    #[test]
+    fn skips_states() {
+        // 5 can only be reached from 4, which is never expanded.
+        let reached = bfs(1, steps, |&n, _| match n {
+            4 => Visit::Skip,
+            5 => Visit::Stop,
+            _ => Visit::Continue,
+        });
+        assert_eq!(reached.path(), None);
+        assert_eq!(reached.cost(&4), Some(2));
+        assert!(!reached.contains(&5));
+    }
+
+    #[test]
+    fn visits_depth_first() {
+        let mut order = vec![];
+        let reached = dfs(
+            1,
+            |&n: &u32| {
+                if n < 8 {
+                    vec![n * 2, n * 2 + 1]
+                } else {
+                    vec![]
+                }
+            },
+            |&n, _| {
+                order.push(n);
+                Visit::Continue
+            },
+        );
+        assert_eq!(order, [1, 2, 4, 8, 9, 5, 10, 11, 3, 6, 12, 13, 7, 14, 15]);
+        assert_eq!(reached.path_to(&11), Some(vec![1, 2, 5, 11]));
+        assert_eq!(reached.cost(&11), Some(3));
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        let grid: Grid<u8> = "1163\n1381\n2136".parse().unwrap();
+        let costs = weighted_grid4(&grid, |_, to| Some(u32::from(grid[to])));
+        let end = (2, 3);
+
+        let reached = dijkstra((0, 0), &costs, until(|&pos| pos == end));
+        assert_eq!(reached.goal_cost(), Some(1 + 2 + 1 + 3 + 6));
+        assert_eq!(
+            reached.path(),
+            Some(vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (2, 3)])
+        );
+
+        let manhattan = |&(row, col): &(usize, usize)| (end.0 - row + end.1 - col) as u32;
+        let guided = astar((0, 0), &costs, manhattan, until(|&pos| pos == end));
+        assert_eq!(guided.goal_cost(), reached.goal_cost());
+        assert!(guided.len() <= reached.len());
+    }
+
+    #[test]
+    fn matches_bfs_with_bidirectional_search() {
+        let halves = |&n: &u32| {
+            let mut previous = vec![n - 1];
+            if n % 2 == 0 {
+                previous.push(n / 2);
+            }
+            previous.retain(|&p| p > 0);
+            previous
+        };
+
+        for goal in 1..200 {
+            let path = bidirectional_bfs(1, goal, steps, halves).unwrap();
+            let expected = bfs(1, steps, until(|&n| n == goal)).goal_cost().unwrap();
+            assert_eq!(path.len(), expected + 1, "path to {goal}: {path:?}");
+            assert_eq!((path[0], *path.last().unwrap()), (1, goal));
+            assert!(path.windows(2).all(|w| steps(&w[0]).contains(&w[1])));
+        }
+        assert_eq!(bidirectional_bfs(5, 1, steps, halves), None);
+    }
+
+    #[test]
+    fn fills_regions() {
+        let grid = maze();
+        assert_eq!(flood_fill(&grid, (0, 3), open(&grid)).len(), 23);
+        assert_eq!(flood_fill(&grid, (0, 2), |_, to| grid[to] == '#').len(), 3);
+    }
+}
@@ -1,17 +1,33 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
-    process::{Command, Output, Stdio},
+    io::{BufRead, BufReader, Read},
+    process::{self, Command, Output, Stdio},
+    thread,
 };
 
+use crate::template::{session, Day};
+
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    Backend(BackendError),
     IoError,
 }
 
+/// Failures reported by the Advent of Code website, classified from the aoc-cli output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackendError {
+    InvalidSession,
+    NotUnlocked,
+    RateLimited,
+    WrongAnswer,
+    AlreadySolved,
+    Network,
+}
+
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -20,11 +36,129 @@ impl Display for AocCommandError {
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::Backend(e) => write!(f, "{e}"),
             AocCommandError::IoError => write!(f, "could not write output files to file system."),
         }
     }
 }
 
+impl Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackendError::InvalidSession => write!(f, "the session cookie is invalid or expired."),
+            BackendError::NotUnlocked => write!(f, "the puzzle has not been unlocked yet."),
+            BackendError::RateLimited => write!(f, "the request was rate limited."),
+            BackendError::WrongAnswer => write!(f, "that's not the right answer."),
+            BackendError::AlreadySolved => write!(f, "this part has already been solved."),
+            BackendError::Network => write!(f, "could not reach adventofcode.com."),
+        }
+    }
+}
+
+impl BackendError {
+    /// Message patterns (lowercase) that identify each failure in the aoc-cli output.
+    fn patterns(self) -> &'static [&'static str] {
+        match self {
+            BackendError::InvalidSession => &[
+                "session cookie",
+                "invalid session",
+                "please log in",
+                "log in to get your puzzle input",
+            ],
+            BackendError::NotUnlocked => {
+                &["still locked", "before it unlocks", "not available yet"]
+            }
+            BackendError::RateLimited => &["answer too recently", "too many requests"],
+            BackendError::WrongAnswer => &["not the right answer"],
+            BackendError::AlreadySolved => &[
+                "already complete",
+                "don't seem to be solving the right level",
+            ],
+            BackendError::Network => &[
+                "http request error",
+                "error sending request",
+                "failed to lookup address",
+                "connection refused",
+                "timed out",
+            ],
+        }
+    }
+
+    #[must_use]
+    pub fn hint(&self) -> &'static str {
+        match self {
+            BackendError::InvalidSession => {
+                "Run `cargo aoc-login <token>` with a fresh session cookie. A ~/.adventofcode.session file takes precedence over it and has to be refreshed or removed."
+            }
+            BackendError::NotUnlocked => {
+                "Puzzles unlock at midnight EST. Use `cargo download <day> --wait` to wait for it."
+            }
+            BackendError::RateLimited => "Wait a minute before submitting or requesting again.",
+            BackendError::WrongAnswer => {
+                "Check your solution against the example input and try again."
+            }
+            BackendError::AlreadySolved => "Nothing to do, this star is already yours.",
+            BackendError::Network => "Check your internet connection and try again.",
+        }
+    }
+
+    /// Process exit code, so scripts can react to specific failures.
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            BackendError::InvalidSession => 10,
+            BackendError::NotUnlocked => 11,
+            BackendError::RateLimited => 12,
+            BackendError::WrongAnswer => 13,
+            BackendError::AlreadySolved => 14,
+            BackendError::Network => 15,
+        }
+    }
+
+    /// Whether retrying the same request later can succeed.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            BackendError::NotUnlocked | BackendError::RateLimited | BackendError::Network
+        )
+    }
+
+    fn classify(output: &str, candidates: &[BackendError]) -> Option<BackendError> {
+        let output = output.to_lowercase();
+        candidates
+            .iter()
+            .copied()
+            .find(|e| e.patterns().iter().any(|p| output.contains(p)))
+    }
+}
+
+impl AocCommandError {
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AocCommandError::Backend(e) => e.exit_code(),
+            AocCommandError::CommandNotFound | AocCommandError::CommandNotCallable => 2,
+            AocCommandError::IoError => 3,
+            AocCommandError::BadExitStatus(_) => 1,
+        }
+    }
+
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        matches!(self, AocCommandError::Backend(e) if e.is_transient())
+    }
+}
+
+/// Prints an error with a hint for classified failures and exits with the matching exit code.
+pub fn exit_with_error(e: &AocCommandError) -> ! {
+    eprintln!("failed to call aoc-cli: {e}");
+    if let AocCommandError::Backend(backend) = e {
+        eprintln!("Hint: {}", backend.hint());
+    }
+    process::exit(e.exit_code());
+}
+
 pub fn check() -> Result<(), AocCommandError> {
     Command::new("aoc")
         .arg("-V")
@@ -33,8 +167,8 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: u8) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+pub fn read(day: Day) -> Result<Output, AocCommandError> {
+    let puzzle_path = day.puzzle_path();
 
     let args = build_args(
         "read",
@@ -49,9 +183,9 @@ pub fn read(day: u8) -> Result<Output, AocCommandError> {
     call_aoc_cli(&args)
 }
 
-pub fn download(day: u8) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+pub fn download(day: Day) -> Result<Output, AocCommandError> {
+    let input_path = day.input_path();
+    let puzzle_path = day.puzzle_path();
 
     let args = build_args(
         "download",
@@ -72,32 +206,57 @@ pub fn download(day: u8) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: u8, part: u8, result: &str) -> Result<Output, AocCommandError> {
+pub fn download_puzzle(day: Day) -> Result<Output, AocCommandError> {
+    let puzzle_path = day.puzzle_path();
+
+    let args = build_args(
+        "download",
+        &[
+            "--overwrite".into(),
+            "--puzzle-only".into(),
+            "--puzzle-file".into(),
+            puzzle_path.to_string(),
+        ],
+        day,
+    );
+
+    let output = call_aoc_cli(&args)?;
+    println!("---");
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(output)
+}
+
+pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(day: u8) -> String {
-    let day_padded = format!("{day:02}");
-    format!("data/inputs/{day_padded}.txt")
-}
-
-fn get_puzzle_path(day: u8) -> String {
-    let day_padded = format!("{day:02}");
-    format!("data/puzzles/{day_padded}.md")
+    // aoc-cli reports rejected answers with a zero exit status.
+    let output = call_aoc_cli(&args)?;
+    let text = String::from_utf8_lossy(&output.stdout) + String::from_utf8_lossy(&output.stderr);
+    match BackendError::classify(
+        &text,
+        &[
+            BackendError::WrongAnswer,
+            BackendError::RateLimited,
+            BackendError::AlreadySolved,
+        ],
+    ) {
+        Some(e) => Err(AocCommandError::Backend(e)),
+        None => Ok(output),
+    }
 }
 
-fn get_year() -> Option<u16> {
+#[must_use]
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
     }
 }
 
-fn build_args(command: &str, args: &[String], day: u8) -> Vec<String> {
+fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
     if let Some(year) = get_year() {
@@ -110,18 +269,137 @@ fn build_args(command: &str, args: &[String], day: u8) -> Vec<String> {
     cmd_args
 }
 
+/// Forwards a stream line by line to the terminal while capturing it.
+/// The session token is redacted from both.
+fn tee(stream: impl Read, token: &str, forward: fn(&str)) -> Vec<u8> {
+    let mut captured = vec![];
+    for line in BufReader::new(stream).lines().map_while(Result::ok) {
+        let line = session::redact(&line, token);
+        forward(&line);
+        captured.extend_from_slice(line.as_bytes());
+        captured.push(b'\n');
+    }
+    captured
+}
+
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    let mut cmd = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output()
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
 
+    let stdout = cmd.stdout.take().ok_or(AocCommandError::IoError)?;
+    let stderr = cmd.stderr.take().ok_or(AocCommandError::IoError)?;
+
+    let token = session::load().map(|s| s.token).unwrap_or_default();
+    let stderr_token = token.clone();
+    let stderr_thread =
+        thread::spawn(move || tee(stderr, &stderr_token, |line| eprintln!("{line}")));
+    let stdout = tee(stdout, &token, |line| println!("{line}"));
+    let stderr = stderr_thread.join().map_err(|_| AocCommandError::IoError)?;
+
+    let status = cmd.wait().map_err(|_| AocCommandError::IoError)?;
+    let output = Output {
+        status,
+        stdout,
+        stderr,
+    };
+
     if output.status.success() {
-        Ok(output)
-    } else {
-        Err(AocCommandError::BadExitStatus(output))
+        return Ok(output);
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout) + String::from_utf8_lossy(&output.stderr);
+    match BackendError::classify(
+        &text,
+        &[
+            BackendError::InvalidSession,
+            BackendError::NotUnlocked,
+            BackendError::RateLimited,
+            BackendError::WrongAnswer,
+            BackendError::AlreadySolved,
+            BackendError::Network,
+        ],
+    ) {
+        Some(e) => Err(AocCommandError::Backend(e)),
+        None => Err(AocCommandError::BadExitStatus(output)),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BackendError;
+
+    const ALL: [BackendError; 6] = [
+        BackendError::InvalidSession,
+        BackendError::NotUnlocked,
+        BackendError::RateLimited,
+        BackendError::WrongAnswer,
+        BackendError::AlreadySolved,
+        BackendError::Network,
+    ];
+
+    #[test]
+    fn classifies_backend_errors() {
+        let cases = [
+            (
+                "[ERROR aoc] 🔔 Invalid session cookie",
+                BackendError::InvalidSession,
+            ),
+            (
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+                BackendError::InvalidSession,
+            ),
+            (
+                "[ERROR aoc] 🔔 Puzzle 6 of 2023 is still locked",
+                BackendError::NotUnlocked,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer.",
+                BackendError::RateLimited,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                BackendError::WrongAnswer,
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                BackendError::AlreadySolved,
+            ),
+            ("[ERROR aoc] 🔔 HTTP request error", BackendError::Network),
+        ];
+
+        for (output, expected) in cases {
+            assert_eq!(
+                BackendError::classify(output, &ALL),
+                Some(expected),
+                "{output}"
+            );
+        }
+    }
+
+    #[test]
+    fn ignores_unknown_output() {
+        assert_eq!(BackendError::classify("something unexpected", &ALL), None);
+    }
+
+    #[test]
+    fn restricts_candidates() {
+        let output = "That's not the right answer.";
+        assert_eq!(
+            BackendError::classify(output, &[BackendError::RateLimited]),
+            None
+        );
+    }
+
+    #[test]
+    fn has_distinct_exit_codes() {
+        let mut codes: Vec<i32> = ALL.iter().map(BackendError::exit_code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), ALL.len());
     }
 }
@@ -0,0 +1,97 @@
+/// Minimal HTTP client for the Advent of Code website.
+/// Requests are authenticated with the same session cookie that aoc-cli uses.
+use std::{env, fmt::Display};
+
+use crate::template::session;
+
+static BASE_URL: &str = "https://adventofcode.com";
+static BASE_URL_ENV: &str = "AOC_BASE_URL";
+static USER_AGENT: &str = concat!("advent_of_code/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum ClientError {
+    SessionNotFound,
+    Http(u16),
+    Transport(String),
+    IoError,
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Run `cargo aoc-login` to set one up."
+            ),
+            ClientError::Http(status) => write!(f, "server responded with status {status}."),
+            ClientError::Transport(e) => write!(f, "request failed: {e}"),
+            ClientError::IoError => write!(f, "could not read response body."),
+        }
+    }
+}
+
+/// Base URL of the website, configurable through `AOC_BASE_URL` (e.g. for a local mock server).
+#[must_use]
+pub fn base_url() -> String {
+    env::var(BASE_URL_ENV)
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or_else(|_| BASE_URL.into())
+}
+
+/// Sends a GET request to `path`, authenticated with the configured session.
+pub fn get(path: &str) -> Result<String, ClientError> {
+    let session = session::load().ok_or(ClientError::SessionNotFound)?;
+    get_with_session(path, &session.token)
+}
+
+/// Sends a GET request to `path`, authenticated with `token`.
+pub fn get_with_session(path: &str, token: &str) -> Result<String, ClientError> {
+    let url = format!("{}{path}", base_url());
+
+    let response = ureq::get(&url)
+        .set("Cookie", &format!("session={token}"))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::Status(status, _) => ClientError::Http(status),
+            ureq::Error::Transport(t) => {
+                ClientError::Transport(session::redact(&t.to_string(), token))
+            }
+        })?;
+
+    response.into_string().map_err(|_| ClientError::IoError)
+}
+
+/// Extracts the account name from the page header, which is only present when logged in.
+#[must_use]
+pub fn parse_account_name(html: &str) -> Option<String> {
+    let (_, rest) = html.split_once(r#"<div class="user">"#)?;
+    let name = rest.split('<').next()?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_account_name;
+
+    #[test]
+    fn parses_account_name() {
+        let html = r#"<header><div class="user">Jane Doe <span class="star-count">12*</span></div></header>"#;
+        assert_eq!(parse_account_name(html), Some("Jane Doe".into()));
+    }
+
+    #[test]
+    fn parses_anonymous_account_name() {
+        let html = r#"<div class="user">(anonymous user #123456)</div>"#;
+        assert_eq!(
+            parse_account_name(html),
+            Some("(anonymous user #123456)".into())
+        );
+    }
+
+    #[test]
+    fn detects_logged_out_page() {
+        let html = r#"<header><a href="/2023/auth/login">[Log In]</a></header>"#;
+        assert_eq!(parse_account_name(html), None);
+    }
+}
@@ -2,14 +2,16 @@ use std::io;
 
 use crate::template::{
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    readme_stars, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 
-pub fn handle(is_release: bool, is_timed: bool) {
+/// Runs all scaffolded solutions. The readme is only touched when asked to:
+/// with `is_timed` in release mode for the benchmark table, and with `update_stars` for the stars table.
+pub fn handle(is_release: bool, is_timed: bool, update_stars: bool) {
     let mut timings: Vec<Timings> = vec![];
 
-    (1..=25).for_each(|day| {
-        if day > 1 {
+    Day::all().for_each(|day| {
+        if day != 1 {
             println!();
         }
 
@@ -40,6 +42,15 @@ pub fn handle(is_release: bool, is_timed: bool) {
             }
         }
     }
+
+    if update_stars {
+        match readme_stars::update(&readme_stars::collect_stars()) {
+            Ok(()) => println!("Successfully updated README with stars."),
+            Err(_) => {
+                eprintln!("Failed to update readme with stars.");
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -55,16 +66,11 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: usize) -> String {
-    let day_padded = format!("{day:02}");
-    format!("./src/bin/{day_padded}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::Error;
+    use crate::template::Day;
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -73,15 +79,11 @@ mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(
-        day: usize,
-        is_timed: bool,
-        is_release: bool,
-    ) -> Result<Vec<String>, Error> {
-        let day_padded = format!("{day:02}");
+    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+        let day_padded = day.padded();
 
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&day.bin_path()).exists() {
             return Ok(vec![]);
         }
 
@@ -129,7 +131,7 @@ mod child_commands {
         Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: usize) -> super::Timings {
+    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
         let mut timings = super::Timings {
             day,
             part_1: None,
@@ -207,6 +209,7 @@ mod child_commands {
     #[cfg(feature = "test_lib")]
     mod tests {
         use super::parse_exec_time;
+        use crate::day;
 
         #[test]
         fn test_well_formed() {
@@ -216,7 +219,7 @@ mod child_commands {
                     "Part 2: 10 (74.13ms @ 99999 samples)".into(),
                     "".into(),
                 ],
-                1,
+                day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
@@ -231,7 +234,7 @@ mod child_commands {
                     "Part 2: 10s (100ms @ 1 samples)".into(),
                     "".into(),
                 ],
-                1,
+                day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
             assert_eq!(res.part_1.unwrap(), "2s");
@@ -246,7 +249,7 @@ mod child_commands {
                     "Part 2: ✖        ".into(),
                     "".into(),
                 ],
-                1,
+                day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
@@ -1,14 +1,97 @@
-use crate::template::aoc_cli;
-use std::process;
+use crate::template::{
+    aoc_cli,
+    commands::scaffold,
+    inputs,
+    unlock::{self, format_countdown},
+    Day,
+};
+use std::{
+    fs,
+    io::{stdout, Write},
+    path::Path,
+    process, thread,
+    time::Duration,
+};
 
-pub fn handle(day: u8) {
+const RETRY_ATTEMPTS: u32 = 5;
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// An input counts as present once it has content; `scaffold` creates empty placeholders.
+fn is_input_present(path: &str) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+pub fn handle(day: Day, force: bool, wait: bool, scaffold: bool) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    let input_path = day.input_path();
+
+    if !force && is_input_present(&input_path) {
+        println!(
+            "Input file \"{input_path}\" already exists, skipping. Use `--force` to overwrite it."
+        );
+        inputs::warn_if_changed(day, &input_path);
+
+        if let Err(e) = aoc_cli::download_puzzle(day) {
+            aoc_cli::exit_with_error(&e);
+        };
+    } else {
+        if wait {
+            wait_for_unlock(day);
+        }
+
+        if let Err(e) = download_with_retries(day, if wait { RETRY_ATTEMPTS } else { 1 }) {
+            aoc_cli::exit_with_error(&e);
+        };
+
+        if let Err(e) = inputs::record(day, &input_path) {
+            eprintln!("failed to record checksum for \"{input_path}\": {e}");
+            process::exit(1);
+        }
+    }
+
+    if scaffold {
+        if Path::new(&day.bin_path()).exists() {
+            println!("Module for day {day} already exists, skipping scaffold.");
+        } else {
+            println!("---");
+            scaffold::handle(&[day], &scaffold::Options::default());
+        }
+    }
+}
+
+/// The site can lag slightly behind the unlock instant, so retry transient failures a few times.
+fn download_with_retries(day: Day, attempts: u32) -> Result<(), aoc_cli::AocCommandError> {
+    let mut attempt = 1;
+    loop {
+        match aoc_cli::download(day) {
+            Ok(_) => return Ok(()),
+            Err(e) if attempt >= attempts || !e.is_transient() => return Err(e),
+            Err(e) => {
+                eprintln!("Download failed ({e}), retrying in {RETRY_DELAY:?}...");
+                thread::sleep(RETRY_DELAY);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Shows a live countdown until the puzzle for `day` unlocks.
+fn wait_for_unlock(day: Day) {
+    let year = aoc_cli::get_year().unwrap_or_else(unlock::current_year);
+    let mut stdout = stdout();
+
+    while let Some(remaining) = unlock::time_until_unlock(year, day.into_inner()) {
+        print!(
+            "\r⏳ Day {day} of {year} unlocks in {}   ",
+            format_countdown(&remaining)
+        );
+        let _ = stdout.flush();
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    println!("\r🎄 Day {day} of {year} is unlocked!                ");
 }
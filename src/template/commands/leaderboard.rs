@@ -0,0 +1,43 @@
+use std::{fs, process};
+
+use crate::template::{
+    aoc_cli, client,
+    leaderboard::{self, Leaderboard, SortOrder},
+    unlock,
+};
+
+pub fn handle(id: u64, file: Option<String>, day: Option<u8>, sort: SortOrder) {
+    if sort == SortOrder::Time && day.is_none() {
+        eprintln!(
+            "Sorting by time requires a day. Format: cargo leaderboard <id> --day 1 --sort time"
+        );
+        process::exit(1);
+    }
+
+    let json = match file {
+        Some(path) => fs::read_to_string(&path).unwrap_or_else(|e| {
+            eprintln!("Failed to read leaderboard file \"{path}\": {e}");
+            process::exit(1);
+        }),
+        None => {
+            let year = aoc_cli::get_year().unwrap_or_else(unlock::current_year);
+            client::get(&format!("/{year}/leaderboard/private/view/{id}.json")).unwrap_or_else(
+                |e| {
+                    eprintln!("Failed to fetch leaderboard: {e}");
+                    process::exit(1);
+                },
+            )
+        }
+    };
+
+    let leaderboard = Leaderboard::parse(&json).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        eprintln!("Make sure your session is valid and you are a member of leaderboard {id}.");
+        process::exit(1);
+    });
+
+    match day {
+        Some(day) => println!("{}", leaderboard::render_day(&leaderboard, day, sort)),
+        None => println!("{}", leaderboard::render_overview(&leaderboard, sort)),
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    io::{stdin, stdout, Write},
+    process,
+};
+
+use crate::template::{
+    aoc_cli::BackendError,
+    client::{self, ClientError},
+    session::{self, Session, EXPIRY_WARNING},
+};
+
+pub fn handle(token: Option<String>, check: bool) {
+    if check {
+        let Some(session) = session::load() else {
+            eprintln!("No session found. Run `cargo aoc-login` to set one up.");
+            process::exit(1);
+        };
+        let account = validate(&session.token);
+        println!(
+            "Session from {} belongs to {account}.",
+            session::describe_source(&session)
+        );
+        warn_if_shadowing(&session);
+        warn_if_expiring(&session);
+        return;
+    }
+
+    let token = session::normalize_token(&token.unwrap_or_else(prompt_token));
+
+    if !session::is_well_formed(&token) {
+        eprintln!("This does not look like a session token. Copy the value of the `session` cookie from adventofcode.com.");
+        process::exit(1);
+    }
+
+    let account = validate(&token);
+
+    match session::store(&token) {
+        Ok(path) => {
+            println!("Logged in as {account}.");
+            println!("🎄 Saved session to \"{}\".", path.display());
+            if let Some(active) = session::load() {
+                println!("Using session from {}.", session::describe_source(&active));
+                warn_if_shadowing(&active);
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to save session: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn prompt_token() -> String {
+    print!("Paste your session cookie: ");
+    let _ = stdout().flush();
+
+    let mut token = String::new();
+    if stdin().read_line(&mut token).is_err() {
+        eprintln!("Failed to read session cookie from stdin.");
+        process::exit(1);
+    }
+    token
+}
+
+/// Requests a page that shows the account name when logged in.
+fn validate(token: &str) -> String {
+    let html = client::get_with_session("/", token).unwrap_or_else(|e| {
+        eprintln!("Failed to validate session: {e}");
+        let code = match e {
+            ClientError::Http(400 | 401 | 403) => BackendError::InvalidSession.exit_code(),
+            _ => BackendError::Network.exit_code(),
+        };
+        process::exit(code);
+    });
+
+    client::parse_account_name(&html).unwrap_or_else(|| {
+        eprintln!("Session is invalid or expired. Log in to adventofcode.com and copy a fresh session cookie.");
+        process::exit(BackendError::InvalidSession.exit_code());
+    })
+}
+
+/// A session in the environment or in `~/.adventofcode.session` wins over the one saved by `aoc-login`.
+fn warn_if_shadowing(session: &Session) {
+    if let Some(saved) = session::shadowed_by(session) {
+        eprintln!(
+            "Warning: the session from {} takes precedence over the one saved to \"{}\". Remove it or update it to use the saved session.",
+            session::describe_source(session),
+            saved.display()
+        );
+    }
+}
+
+fn warn_if_expiring(session: &Session) {
+    match session::time_until_expiry(session) {
+        Some(remaining) if remaining.is_zero() => eprintln!(
+            "Warning: this session was saved more than a month ago and may expire any moment."
+        ),
+        Some(remaining) if remaining < EXPIRY_WARNING => eprintln!(
+            "Warning: this session will likely expire in {} days. Run `cargo aoc-login` with a fresh cookie soon.",
+            remaining.as_secs() / 86_400 + 1
+        ),
+        _ => {}
+    }
+}
@@ -1,5 +1,8 @@
 pub mod all;
 pub mod download;
+pub mod leaderboard;
+pub mod login;
 pub mod read;
+pub mod readme;
 pub mod scaffold;
 pub mod solve;
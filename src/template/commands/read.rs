@@ -1,15 +1,77 @@
-use std::process;
+use std::{
+    env, fs,
+    io::{stdout, IsTerminal, Write},
+    process::{self, Command, Stdio},
+};
 
-use crate::template::aoc_cli;
+use terminal_size::{terminal_size, Height, Width};
+
+use crate::template::{aoc_cli, markdown, Day};
+
+const DEFAULT_WIDTH: usize = 80;
+
+pub fn handle(day: Day, offline: bool) {
+    if offline {
+        return read_offline(day);
+    }
 
-pub fn handle(day: u8) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
     if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+        aoc_cli::exit_with_error(&e);
+    };
+}
+
+/// Renders the cached puzzle description without calling aoc-cli.
+fn read_offline(day: Day) {
+    let puzzle_path = day.puzzle_path();
+
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        eprintln!("No cached puzzle found at \"{puzzle_path}\". Run `cargo download {day}` first.");
         process::exit(1);
     };
+
+    let size = terminal_size();
+    let width = size.map_or(DEFAULT_WIDTH, |(Width(w), _)| w as usize);
+    let rendered = markdown::render(&puzzle, width);
+
+    match size {
+        Some((_, Height(h))) if rendered.lines().count() > h as usize => page(&rendered),
+        _ => println!("{rendered}"),
+    }
+}
+
+/// Pipes output through `$PAGER` (default: `less -R`), falling back to printing it directly.
+fn page(text: &str) {
+    if !stdout().is_terminal() {
+        println!("{text}");
+        return;
+    }
+
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+    let mut parts = pager.split_whitespace();
+
+    let Some(program) = parts.next() else {
+        println!("{text}");
+        return;
+    };
+
+    let child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn();
+
+    match child {
+        Ok(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                // the pager closing early (e.g. quitting `less`) results in a broken pipe we can ignore.
+                let _ = writeln!(stdin, "{text}");
+            }
+            let _ = child.wait();
+        }
+        Err(_) => println!("{text}"),
+    }
 }
@@ -0,0 +1,21 @@
+use std::process;
+
+use crate::template::readme_stars;
+
+pub fn handle() {
+    let stars = readme_stars::collect_stars();
+
+    match readme_stars::update(&stars) {
+        Ok(()) => println!(
+            "Successfully updated README with {} stars.",
+            stars
+                .iter()
+                .map(|s| u8::from(s.part_1) + u8::from(s.part_2))
+                .sum::<u8>()
+        ),
+        Err(e) => {
+            eprintln!("Failed to update readme with stars: {e:?}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,93 +1,455 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fmt::Display,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
+    str::FromStr,
 };
 
-const MODULE_TEMPLATE: &str = r#"pub fn part_one(_input: &str) -> Option<u32> {
-    None
+use crate::template::{aoc_cli, unlock, Day, ANSI_BOLD, ANSI_RESET};
+
+static TEMPLATES_DIR: &str = "templates";
+static DEFAULT_TEMPLATE: &str = "default";
+
+/// Built in from `templates/default.rs`, used when the `templates/` directory does not provide a default template.
+const MODULE_TEMPLATE: &str = include_str!("../../../templates/default.rs");
+
+/// Return type of a scaffolded solution part, wrapped in an `Option`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AnswerType {
+    #[default]
+    U32,
+    U64,
+    U128,
+    I32,
+    I64,
+    I128,
+    Usize,
+    Isize,
+    String,
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
-    None
+impl FromStr for AnswerType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "u32" => Ok(AnswerType::U32),
+            "u64" => Ok(AnswerType::U64),
+            "u128" => Ok(AnswerType::U128),
+            "i32" => Ok(AnswerType::I32),
+            "i64" => Ok(AnswerType::I64),
+            "i128" => Ok(AnswerType::I128),
+            "usize" => Ok(AnswerType::Usize),
+            "isize" => Ok(AnswerType::Isize),
+            "String" | "string" => Ok(AnswerType::String),
+            _ => Err(format!(
+                "unsupported answer type \"{s}\", expected one of u32, u64, u128, i32, i64, i128, usize, isize or String"
+            )),
+        }
+    }
 }
 
-advent_of_code::main!(DAY);
+impl Display for AnswerType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AnswerType::U32 => "u32",
+            AnswerType::U64 => "u64",
+            AnswerType::U128 => "u128",
+            AnswerType::I32 => "i32",
+            AnswerType::I64 => "i64",
+            AnswerType::I128 => "i128",
+            AnswerType::Usize => "usize",
+            AnswerType::Isize => "isize",
+            AnswerType::String => "String",
+        };
+        write!(f, "{name}")
+    }
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[derive(Debug)]
+pub enum TemplateError {
+    NotFound(String),
+    UnknownPlaceholder(String),
+    Unclosed,
+}
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::NotFound(path) => write!(f, "template \"{path}\" does not exist."),
+            TemplateError::UnknownPlaceholder(key) => {
+                write!(f, "unknown placeholder {{{{{key}}}}} in template.")
+            }
+            TemplateError::Unclosed => write!(f, "unclosed placeholder in template."),
+        }
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+/// Loads `templates/<name>.rs`, falling back to the built-in template for the default.
+fn load_template(name: Option<&str>) -> Result<String, TemplateError> {
+    let name = name.unwrap_or(DEFAULT_TEMPLATE);
+    let path = Path::new(TEMPLATES_DIR).join(format!("{name}.rs"));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(_) if name == DEFAULT_TEMPLATE => Ok(MODULE_TEMPLATE.into()),
+        Err(_) => Err(TemplateError::NotFound(path.display().to_string())),
+    }
+}
+
+/// Replaces `{{key}}` placeholders with their values. `{{{{` is an escaped, literal `{{`.
+fn render_template(template: &str, values: &[(&str, String)]) -> Result<String, TemplateError> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        if rest[start..].starts_with("{{{{") {
+            out.push_str("{{");
+            rest = &rest[start + 4..];
+            continue;
+        }
+        let end = rest[start..].find("}}").ok_or(TemplateError::Unclosed)? + start;
+        let key = rest[start + 2..end].trim();
+        let (_, value) = values
+            .iter()
+            .find(|(k, _)| *k == key)
+            .ok_or_else(|| TemplateError::UnknownPlaceholder(key.into()))?;
+        out.push_str(value);
+        rest = &rest[end + 2..];
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Reads the puzzle title from a downloaded description, e.g. `--- Day 1: Trebuchet?! ---`.
+fn parse_title(puzzle: &str) -> Option<String> {
+    let line = puzzle.lines().find(|l| l.contains("--- Day "))?;
+    let (_, title) = line.split_once(": ")?;
+    let title = title.trim().trim_end_matches('-').trim();
+    Some(title.replace('\\', ""))
+}
+
+fn template_values(
+    day: Day,
+    type_one: AnswerType,
+    type_two: AnswerType,
+) -> Vec<(&'static str, String)> {
+    let title = fs::read_to_string(day.puzzle_path())
+        .ok()
+        .and_then(|puzzle| parse_title(&puzzle))
+        .unwrap_or_else(|| format!("Day {day}"));
+    let year = aoc_cli::get_year().unwrap_or_else(unlock::current_year);
+
+    vec![
+        ("day", day.to_string()),
+        ("day_padded", day.padded()),
+        ("year", year.to_string()),
+        ("title", title),
+        ("type_one", type_one.to_string()),
+        ("type_two", type_two.to_string()),
+    ]
+}
+
+/// Days to scaffold, e.g. `5`, `6-10` or `1,3,5-7`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Days(pub Vec<Day>);
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |d: &str| d.parse::<Day>().map_err(|e| e.to_string());
+
+        let mut days = vec![];
+        for part in s.split(',') {
+            match part.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end) = (parse_day(start)?, parse_day(end)?);
+                    if start > end {
+                        return Err(format!("invalid range \"{part}\"."));
+                    }
+                    days.extend(Day::all().filter(|day| (start..=end).contains(day)));
+                }
+                None => days.push(parse_day(part)?),
+            }
+        }
+
+        days.sort_unstable();
+        days.dedup();
+        Ok(Days(days))
+    }
+}
+
+#[derive(Debug)]
+enum Outcome {
+    Created,
+    WouldCreate,
+    Replaced(String),
+    WouldReplace,
+    Skipped,
+    Failed(io::Error),
+}
+
+struct ScaffoldFile {
+    name: &'static str,
+    path: String,
+    contents: String,
+    /// Only generated files are regenerated by `--force`, data files are kept.
+    is_generated: bool,
+}
+
+impl ScaffoldFile {
+    fn description(&self) -> String {
+        if self.contents.is_empty() {
+            format!("empty {}", self.name)
+        } else {
+            self.name.into()
+        }
+    }
+
+    fn report(&self, outcome: &Outcome) -> bool {
+        let (description, path) = (self.description(), &self.path);
+        match outcome {
+            Outcome::Created => println!("Created {description} \"{path}\""),
+            Outcome::WouldCreate => println!("Would create {description} \"{path}\""),
+            Outcome::Replaced(backup) => {
+                println!("Regenerated {description} \"{path}\" (backup: \"{backup}\")");
+            }
+            Outcome::WouldReplace => {
+                println!("Would back up and regenerate {description} \"{path}\"")
+            }
+            Outcome::Skipped => println!("Skipped {} \"{path}\": file already exists.", self.name),
+            Outcome::Failed(e) => {
+                eprintln!("Failed to create {} \"{path}\": {e}", self.name);
+                return false;
+            }
+        }
+        true
     }
 }
-"#;
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Copies `path` to the first free `<path>.bak`, `<path>.bak.1`, ... and returns the backup path.
+fn backup_file(path: &str) -> Result<String, io::Error> {
+    let backup = (0..)
+        .map(|i| match i {
+            0 => format!("{path}.bak"),
+            i => format!("{path}.bak.{i}"),
+        })
+        .find(|p| !Path::new(p).exists())
+        .unwrap_or_else(|| format!("{path}.bak"));
+    fs::copy(path, &backup)?;
+    Ok(backup)
 }
 
-pub fn handle(day: u8) {
-    let day_padded = format!("{day:02}");
+fn write_file(file: &ScaffoldFile, dry_run: bool, force: bool) -> Outcome {
+    let exists = Path::new(&file.path).exists();
+
+    match (exists, dry_run) {
+        (false, true) => Outcome::WouldCreate,
+        (false, false) => match safe_create_file(&file.path)
+            .and_then(|mut f| f.write_all(file.contents.as_bytes()))
+        {
+            Ok(()) => Outcome::Created,
+            Err(e) => Outcome::Failed(e),
+        },
+        (true, _) if !(force && file.is_generated) => Outcome::Skipped,
+        (true, true) => Outcome::WouldReplace,
+        (true, false) => match backup_file(&file.path)
+            .and_then(|backup| fs::write(&file.path, &file.contents).map(|()| backup))
+        {
+            Ok(backup) => Outcome::Replaced(backup),
+            Err(e) => Outcome::Failed(e),
+        },
+    }
+}
 
-    let input_path = format!("data/inputs/{day_padded}.txt");
-    let example_path = format!("data/examples/{day_padded}.txt");
-    let module_path = format!("src/bin/{day_padded}.rs");
+fn get_files(day: Day, module: String) -> [ScaffoldFile; 3] {
+    [
+        ScaffoldFile {
+            name: "module file",
+            path: day.bin_path(),
+            contents: module,
+            is_generated: true,
+        },
+        ScaffoldFile {
+            name: "input file",
+            path: day.input_path(),
+            contents: String::new(),
+            is_generated: false,
+        },
+        ScaffoldFile {
+            name: "example file",
+            path: day.example_path(),
+            contents: String::new(),
+            is_generated: false,
+        },
+    ]
+}
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
+#[derive(Default)]
+pub struct Options<'a> {
+    pub template: Option<&'a str>,
+    pub type_one: AnswerType,
+    pub type_two: AnswerType,
+    pub dry_run: bool,
+    pub force: bool,
+}
+
+pub fn handle(days: &[Day], options: &Options) {
+    let template = match load_template(options.template) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("Failed to load module template: {e}");
             process::exit(1);
         }
     };
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+    let mut is_success = true;
+
+    for (i, &day) in days.iter().enumerate() {
+        if days.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+
+        let values = template_values(day, options.type_one, options.type_two);
+        let module = match render_template(&template, &values) {
+            Ok(module) => module,
+            Err(e) => {
+                eprintln!("Failed to render module template: {e}");
+                process::exit(1);
+            }
+        };
+
+        for file in get_files(day, module) {
+            let outcome = write_file(&file, options.dry_run, options.force);
+            is_success &= file.report(&outcome);
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    if !options.dry_run {
+        println!("---");
+        match days {
+            [day] => println!("🎄 Type `cargo solve {day:02}` to run your solution."),
+            _ => println!("🎄 Type `cargo solve <day>` to run your solutions."),
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    if !is_success {
+        process::exit(1);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_title, render_template, AnswerType, Days, MODULE_TEMPLATE};
+    use crate::template::Day;
+
+    fn values() -> Vec<(&'static str, String)> {
+        vec![
+            ("day", "5".into()),
+            ("day_padded", "05".into()),
+            ("year", "2023".into()),
+            ("title", "If You Give A Seed A Fertilizer".into()),
+            ("type_one", "u64".into()),
+            ("type_two", "String".into()),
+        ]
+    }
+
+    #[test]
+    fn renders_answer_types() {
+        let result = render_template(MODULE_TEMPLATE, &values()).unwrap();
+        assert!(result.contains("pub fn part_one(_input: &str) -> Option<u64> {"));
+        assert!(result.contains("pub fn part_two(_input: &str) -> Option<String> {"));
+    }
+
+    #[test]
+    fn parses_answer_types() {
+        assert_eq!("u64".parse::<AnswerType>(), Ok(AnswerType::U64));
+        assert_eq!("String".parse::<AnswerType>(), Ok(AnswerType::String));
+        assert_eq!(AnswerType::default().to_string(), "u32");
+        assert!("f64".parse::<AnswerType>().is_err());
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let result = render_template(
+            "// {{year}} day {{ day_padded }}: {{title}}\nadvent_of_code::main!({{day}});",
+            &values(),
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            "// 2023 day 05: If You Give A Seed A Fertilizer\nadvent_of_code::main!(5);"
+        );
+    }
+
+    #[test]
+    fn keeps_plain_text_untouched() {
+        let result = render_template(MODULE_TEMPLATE, &values()).unwrap();
+        assert!(result.contains("advent_of_code::main!(5);"));
+        let template = "const DAY: u32 = {{day}};";
+        assert_eq!(
+            render_template(template, &values()).unwrap(),
+            "const DAY: u32 = 5;"
+        );
+    }
+
+    #[test]
+    fn renders_escaped_braces() {
+        let template = "// {{{{day}} is day {{day}}, {{{{{{{{ stays }}\nprintln!(\"{{{{}}\");";
+        assert_eq!(
+            render_template(template, &values()).unwrap(),
+            "// {{day}} is day 5, {{{{ stays }}\nprintln!(\"{{}}\");"
+        );
+    }
+
+    #[test]
+    fn builds_in_the_default_template() {
+        let template = std::fs::read_to_string("templates/default.rs").unwrap();
+        assert_eq!(MODULE_TEMPLATE, template);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_unknown_placeholder() {
+        render_template("{{dya}}", &values()).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_on_unclosed_placeholder() {
+        render_template("{{day", &values()).unwrap();
+    }
+
+    #[test]
+    fn parses_title() {
+        let puzzle = "\\--- Day 1: Trebuchet?! ---\n----------\n\nSomething is wrong.";
+        assert_eq!(parse_title(puzzle), Some("Trebuchet?!".into()));
+        assert_eq!(parse_title("no title"), None);
     }
 
-    println!("---");
-    println!(
-        "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
-    );
+    #[test]
+    fn parses_days() {
+        let parse = |s: &str| {
+            s.parse::<Days>()
+                .map(|days| days.0.into_iter().map(Day::into_inner).collect::<Vec<_>>())
+        };
+        assert_eq!(parse("5"), Ok(vec![5]));
+        assert_eq!(parse("6-10"), Ok(vec![6, 7, 8, 9, 10]));
+        assert_eq!(parse("1,3,5-6,3"), Ok(vec![1, 3, 5, 6]));
+        assert!(parse("10-6").is_err());
+        assert!(parse("x").is_err());
+        assert!(parse("20-30").is_err());
+    }
 }
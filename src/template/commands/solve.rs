@@ -1,11 +1,22 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-pub fn handle(day: u8, release: bool, time: bool, submit_part: Option<u8>) {
-    let day_padded = format!("{day:02}");
+use crate::template::{inputs, Day};
+
+/// Cargo profile that inherits `release` but keeps integer overflow checks enabled.
+static OVERFLOW_PROFILE: &str = "release-overflow";
+
+pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>, check_overflow: bool) {
+    let day_padded = day.padded();
+
+    inputs::warn_if_changed(day, &day.input_path());
 
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day_padded];
 
-    if release {
+    if check_overflow {
+        println!("Running with overflow checks in an optimized build...");
+        cmd_args.push("--profile".to_string());
+        cmd_args.push(OVERFLOW_PROFILE.to_string());
+    } else if release {
         cmd_args.push("--release".to_string());
     }
 
@@ -27,5 +38,12 @@ pub fn handle(day: u8, release: bool, time: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    if !status.success() {
+        if check_overflow {
+            eprintln!("Hint: if the solution panicked with \"attempt to add with overflow\" or similar, scaffold it with a wider answer type, e.g. `--type u64`.");
+        }
+        process::exit(status.code().unwrap_or(1));
+    }
 }
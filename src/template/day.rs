@@ -0,0 +1,183 @@
+/// A validated day of the advent calendar.
+use std::{error::Error, fmt::Display, str::FromStr};
+
+pub const FIRST_DAY: u8 = 1;
+pub const LAST_DAY: u8 = 25;
+
+/// A day between 1 and 25.
+/// Formatting flags are forwarded to the number, so `{day:02}` yields the zero-padded day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Day(u8);
+
+impl Day {
+    /// Creates a [`Day`] if `day` is between 1 and 25.
+    #[must_use]
+    pub const fn new(day: u8) -> Option<Self> {
+        if day < FIRST_DAY || day > LAST_DAY {
+            return None;
+        }
+        Some(Day(day))
+    }
+
+    /// Used by the [`crate::day`] macro, which validates the day at compile time.
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn __new_unchecked(day: u8) -> Self {
+        Day(day)
+    }
+
+    #[must_use]
+    pub const fn into_inner(self) -> u8 {
+        self.0
+    }
+
+    /// Iterates over all days of the calendar.
+    pub fn all() -> impl Iterator<Item = Day> {
+        (FIRST_DAY..=LAST_DAY).map(Day)
+    }
+
+    /// The zero-padded day, e.g. `05`.
+    #[must_use]
+    pub fn padded(self) -> String {
+        format!("{self:02}")
+    }
+
+    /// Path of the solution binary, e.g. `src/bin/05.rs`.
+    #[must_use]
+    pub fn bin_path(self) -> String {
+        format!("src/bin/{}.rs", self.padded())
+    }
+
+    /// Path of a file in the `data/` directory, e.g. `data/inputs/05.txt`.
+    #[must_use]
+    pub fn data_path(self, folder: &str) -> String {
+        format!("data/{folder}/{}.txt", self.padded())
+    }
+
+    #[must_use]
+    pub fn input_path(self) -> String {
+        self.data_path("inputs")
+    }
+
+    #[must_use]
+    pub fn example_path(self) -> String {
+        self.data_path("examples")
+    }
+
+    #[must_use]
+    pub fn puzzle_path(self) -> String {
+        format!("data/puzzles/{}.md", self.padded())
+    }
+}
+
+impl Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq<u8> for Day {
+    fn eq(&self, other: &u8) -> bool {
+        self.0 == *other
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseDayError {
+    NotANumber(String),
+    OutOfRange(u8),
+}
+
+impl Display for ParseDayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseDayError::NotANumber(s) => write!(
+                f,
+                "\"{s}\" is not a day. Expected a number between {FIRST_DAY} and {LAST_DAY}."
+            ),
+            ParseDayError::OutOfRange(day) => write!(
+                f,
+                "day {day} does not exist. Expected a number between {FIRST_DAY} and {LAST_DAY}."
+            ),
+        }
+    }
+}
+
+impl Error for ParseDayError {}
+
+impl TryFrom<u8> for Day {
+    type Error = ParseDayError;
+
+    fn try_from(day: u8) -> Result<Self, Self::Error> {
+        Day::new(day).ok_or(ParseDayError::OutOfRange(day))
+    }
+}
+
+impl FromStr for Day {
+    type Err = ParseDayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let day = s
+            .trim()
+            .parse::<u8>()
+            .map_err(|_| ParseDayError::NotANumber(s.to_string()))?;
+        Day::try_from(day)
+    }
+}
+
+/// Creates a [`Day`], checking at compile time that the day is between 1 and 25.
+#[macro_export]
+macro_rules! day {
+    ($day:expr) => {{
+        const _: () = assert!(
+            $day >= $crate::template::day::FIRST_DAY && $day <= $crate::template::day::LAST_DAY,
+            "invalid day number, expected a number between 1 and 25"
+        );
+        $crate::template::Day::__new_unchecked($day)
+    }};
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Day, ParseDayError};
+
+    #[test]
+    fn parses_valid_days() {
+        assert_eq!("1".parse::<Day>(), Ok(Day(1)));
+        assert_eq!("05".parse::<Day>(), Ok(Day(5)));
+        assert_eq!("25".parse::<Day>(), Ok(Day(25)));
+    }
+
+    #[test]
+    fn rejects_invalid_days() {
+        assert_eq!("0".parse::<Day>(), Err(ParseDayError::OutOfRange(0)));
+        assert_eq!("26".parse::<Day>(), Err(ParseDayError::OutOfRange(26)));
+        assert_eq!(
+            "x".parse::<Day>(),
+            Err(ParseDayError::NotANumber("x".into()))
+        );
+        assert_eq!(
+            "300".parse::<Day>(),
+            Err(ParseDayError::NotANumber("300".into()))
+        );
+    }
+
+    #[test]
+    fn formats_days() {
+        let day = crate::day!(5);
+        assert_eq!(day.to_string(), "5");
+        assert_eq!(day.padded(), "05");
+        assert_eq!(day.bin_path(), "src/bin/05.rs");
+        assert_eq!(day.input_path(), "data/inputs/05.txt");
+        assert_eq!(day.example_path(), "data/examples/05.txt");
+        assert_eq!(day.puzzle_path(), "data/puzzles/05.md");
+    }
+
+    #[test]
+    fn iterates_all_days() {
+        let days: Vec<_> = Day::all().collect();
+        assert_eq!(days.len(), 25);
+        assert_eq!(days.first(), Some(&Day(1)));
+        assert_eq!(days.last(), Some(&Day(25)));
+    }
+}
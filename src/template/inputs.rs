@@ -0,0 +1,136 @@
+/// Module that keeps downloaded inputs normalised and tracks their checksums.
+/// Checksums are stored in a `sha256sum`-compatible file next to the inputs.
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fmt::Write, fs, io, path::Path};
+
+use crate::template::Day;
+
+static CHECKSUMS_PATH: &str = "data/inputs/checksums";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The input matches the recorded checksum.
+    Unchanged,
+    /// The input differs from the recorded checksum.
+    Changed,
+    /// No checksum has been recorded for this input.
+    Untracked,
+}
+
+/// Converts CRLF line endings to LF and collapses trailing blank lines into a single newline.
+#[must_use]
+pub fn normalize(s: &str) -> String {
+    let mut normalized = s.replace("\r\n", "\n");
+    normalized.truncate(normalized.trim_end_matches('\n').len());
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+#[must_use]
+pub fn sha256(s: &str) -> String {
+    Sha256::digest(s.as_bytes())
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+fn file_name(day: Day) -> String {
+    format!("{}.txt", day.padded())
+}
+
+fn parse_checksums(s: &str) -> BTreeMap<String, String> {
+    s.lines()
+        .filter_map(|line| {
+            let (hash, name) = line.split_once("  ")?;
+            Some((name.trim().to_string(), hash.trim().to_string()))
+        })
+        .collect()
+}
+
+fn serialize_checksums(checksums: &BTreeMap<String, String>) -> String {
+    checksums
+        .iter()
+        .map(|(name, hash)| format!("{hash}  {name}\n"))
+        .collect()
+}
+
+fn read_checksums() -> Result<BTreeMap<String, String>, io::Error> {
+    if !Path::new(CHECKSUMS_PATH).exists() {
+        return Ok(BTreeMap::new());
+    }
+    Ok(parse_checksums(&fs::read_to_string(CHECKSUMS_PATH)?))
+}
+
+/// Normalises the input file in place and records its checksum.
+pub fn record(day: Day, path: &str) -> Result<(), io::Error> {
+    let contents = normalize(&fs::read_to_string(path)?);
+    fs::write(path, &contents)?;
+
+    let mut checksums = read_checksums()?;
+    checksums.insert(file_name(day), sha256(&contents));
+    fs::write(CHECKSUMS_PATH, serialize_checksums(&checksums))
+}
+
+/// Compares the normalised input file with its recorded checksum.
+pub fn verify(day: Day, path: &str) -> Result<Status, io::Error> {
+    let checksums = read_checksums()?;
+    let Some(expected) = checksums.get(&file_name(day)) else {
+        return Ok(Status::Untracked);
+    };
+
+    let contents = normalize(&fs::read_to_string(path)?);
+    if &sha256(&contents) == expected {
+        Ok(Status::Unchanged)
+    } else {
+        Ok(Status::Changed)
+    }
+}
+
+/// Prints a warning if the input for a day no longer matches its recorded checksum.
+pub fn warn_if_changed(day: Day, path: &str) {
+    if let Ok(Status::Changed) = verify(day, path) {
+        eprintln!(
+            "Warning: \"{path}\" has changed since it was downloaded. Run `cargo download {day} --force` to restore it."
+        );
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{normalize, parse_checksums, serialize_checksums, sha256};
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize("a\r\nb\r\n\r\nc\r\n"), "a\nb\n\nc\n");
+    }
+
+    #[test]
+    fn normalizes_trailing_newlines() {
+        assert_eq!(normalize("a\nb"), "a\nb\n");
+        assert_eq!(normalize("a\nb\n\n\n"), "a\nb\n");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn hashes_sha256() {
+        assert_eq!(
+            sha256("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn roundtrips_checksums() {
+        let checksums = parse_checksums("aaaa  01.txt\nbbbb  05.txt\n");
+        assert_eq!(checksums["01.txt"], "aaaa");
+        assert_eq!(checksums["05.txt"], "bbbb");
+        assert_eq!(
+            serialize_checksums(&checksums),
+            "aaaa  01.txt\nbbbb  05.txt\n"
+        );
+    }
+}
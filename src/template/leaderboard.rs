@@ -0,0 +1,321 @@
+/// Module that parses and renders private leaderboards.
+/// The JSON format is documented on the private leaderboard page of the Advent of Code website.
+use serde::Deserialize;
+use std::{cmp::Reverse, collections::HashMap, fmt::Display, str::FromStr, time::Duration};
+
+use crate::template::{unlock, ANSI_BOLD, ANSI_RESET};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse leaderboard: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub last_star_ts: i64,
+    #[serde(default)]
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Highest local score first.
+    Score,
+    /// Most stars first.
+    Stars,
+    /// Fastest time-to-solve for a given day first.
+    Time,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "score" => Ok(SortOrder::Score),
+            "stars" => Ok(SortOrder::Stars),
+            "time" => Ok(SortOrder::Time),
+            _ => Err(format!(
+                "unknown sort order \"{s}\", expected score, stars or time"
+            )),
+        }
+    }
+}
+
+impl Member {
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Timestamp at which the member got the star for a day and part.
+    #[must_use]
+    pub fn star_ts(&self, day: u8, part: u8) -> Option<i64> {
+        self.completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())
+            .map(|s| s.get_star_ts)
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(s: &str) -> Result<Leaderboard, Error> {
+        serde_json::from_str(s).map_err(|e| Error::Parser(e.to_string()))
+    }
+
+    fn year(&self) -> u16 {
+        self.event
+            .parse()
+            .unwrap_or_else(|_| unlock::current_year())
+    }
+
+    /// Highest day any member has a star for.
+    fn max_day(&self) -> u8 {
+        self.members
+            .values()
+            .flat_map(|m| m.completion_day_level.keys())
+            .filter_map(|d| d.parse().ok())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Time from unlock until the member got the star for a day and part.
+    #[must_use]
+    pub fn time_to_solve(&self, member: &Member, day: u8, part: u8) -> Option<Duration> {
+        let ts = member.star_ts(day, part)?;
+        let elapsed = ts - unlock::unlock_timestamp(self.year(), day);
+        u64::try_from(elapsed).ok().map(Duration::from_secs)
+    }
+
+    /// Members in the requested order. Sorting by time requires a day and ranks members by part 2,
+    /// then part 1 completion, putting members without stars for that day last.
+    #[must_use]
+    pub fn sorted_members(&self, order: SortOrder, day: Option<u8>) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|m| (m.display_name(), m.id));
+
+        match (order, day) {
+            (SortOrder::Time, Some(day)) => members.sort_by_key(|m| {
+                let part_2 = self.time_to_solve(m, day, 2);
+                let part_1 = self.time_to_solve(m, day, 1);
+                (part_2.is_none(), part_2, part_1.is_none(), part_1)
+            }),
+            (SortOrder::Stars, _) => {
+                members.sort_by_key(|m| (Reverse(m.stars), Reverse(m.local_score)));
+            }
+            _ => members.sort_by_key(|m| (Reverse(m.local_score), Reverse(m.stars))),
+        }
+
+        members
+    }
+}
+
+fn format_solve_time(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".into(), |d| unlock::format_countdown(&d))
+}
+
+fn star_symbol(member: &Member, day: u8) -> char {
+    match (member.star_ts(day, 1), member.star_ts(day, 2)) {
+        (Some(_), Some(_)) => '★',
+        (Some(_), None) => '☆',
+        _ => '·',
+    }
+}
+
+/// Renders an overview with scores and stars for every day.
+#[must_use]
+pub fn render_overview(leaderboard: &Leaderboard, order: SortOrder) -> String {
+    let max_day = leaderboard.max_day();
+    let members = leaderboard.sorted_members(order, None);
+    let name_width = members
+        .iter()
+        .map(|m| m.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    let days: String = (1..=max_day).map(|d| char::from(b'0' + d % 10)).collect();
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}{:>3}  {:<name_width$}  {:>5}  {:>5}  {days}{ANSI_RESET}",
+        "#", "Name", "Score", "Stars"
+    )];
+
+    for (rank, member) in members.iter().enumerate() {
+        let stars: String = (1..=max_day).map(|d| star_symbol(member, d)).collect();
+        lines.push(format!(
+            "{:>3}  {:<name_width$}  {:>5}  {:>5}  {stars}",
+            rank + 1,
+            member.display_name(),
+            member.local_score,
+            member.stars
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// Renders star timestamps and time-to-solve for a single day.
+#[must_use]
+pub fn render_day(leaderboard: &Leaderboard, day: u8, order: SortOrder) -> String {
+    let members = leaderboard.sorted_members(order, Some(day));
+    let name_width = members
+        .iter()
+        .map(|m| m.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}{:>3}  {:<name_width$}  {:>5}  {:>19}  {:>10}  {:>19}  {:>10}{ANSI_RESET}",
+        "#", "Name", "Score", "Part 1 (EST)", "Part 1", "Part 2 (EST)", "Part 2"
+    )];
+
+    for (rank, member) in members.iter().enumerate() {
+        let ts = |part| {
+            member
+                .star_ts(day, part)
+                .map_or_else(|| "-".into(), unlock::format_timestamp)
+        };
+        lines.push(format!(
+            "{:>3}  {:<name_width$}  {:>5}  {:>19}  {:>10}  {:>19}  {:>10}",
+            rank + 1,
+            member.display_name(),
+            member.local_score,
+            ts(1),
+            format_solve_time(leaderboard.time_to_solve(member, day, 1)),
+            ts(2),
+            format_solve_time(leaderboard.time_to_solve(member, day, 2)),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render_day, render_overview, Leaderboard, SortOrder};
+
+    // 2023-12-01T05:00:00Z
+    const DAY_1_UNLOCK: i64 = 1_701_406_800;
+
+    fn get_mock_leaderboard() -> Leaderboard {
+        let json = format!(
+            r#"{{
+                "event": "2023",
+                "owner_id": 1,
+                "members": {{
+                    "1": {{
+                        "id": 1, "name": "alice", "stars": 3, "local_score": 10, "global_score": 0,
+                        "last_star_ts": {p2a},
+                        "completion_day_level": {{
+                            "1": {{ "1": {{ "get_star_ts": {p1a}, "star_index": 0 }}, "2": {{ "get_star_ts": {p2a}, "star_index": 1 }} }},
+                            "2": {{ "1": {{ "get_star_ts": {d2}, "star_index": 2 }} }}
+                        }}
+                    }},
+                    "2": {{
+                        "id": 2, "name": null, "stars": 2, "local_score": 12, "global_score": 0,
+                        "last_star_ts": {p2b},
+                        "completion_day_level": {{
+                            "1": {{ "1": {{ "get_star_ts": {p1b}, "star_index": 0 }}, "2": {{ "get_star_ts": {p2b}, "star_index": 1 }} }}
+                        }}
+                    }},
+                    "3": {{
+                        "id": 3, "name": "carol", "stars": 0, "local_score": 0, "global_score": 0,
+                        "last_star_ts": 0, "completion_day_level": {{}}
+                    }}
+                }}
+            }}"#,
+            p1a = DAY_1_UNLOCK + 300,
+            p2a = DAY_1_UNLOCK + 900,
+            p1b = DAY_1_UNLOCK + 120,
+            p2b = DAY_1_UNLOCK + 600,
+            d2 = DAY_1_UNLOCK + 86_400 + 60,
+        );
+        Leaderboard::parse(&json).unwrap()
+    }
+
+    fn names(leaderboard: &Leaderboard, order: SortOrder, day: Option<u8>) -> Vec<String> {
+        leaderboard
+            .sorted_members(order, day)
+            .iter()
+            .map(|m| m.display_name())
+            .collect()
+    }
+
+    #[test]
+    fn sorts_by_score() {
+        let leaderboard = get_mock_leaderboard();
+        assert_eq!(
+            names(&leaderboard, SortOrder::Score, None),
+            vec!["(anonymous user #2)", "alice", "carol"]
+        );
+    }
+
+    #[test]
+    fn sorts_by_stars() {
+        let leaderboard = get_mock_leaderboard();
+        assert_eq!(
+            names(&leaderboard, SortOrder::Stars, None),
+            vec!["alice", "(anonymous user #2)", "carol"]
+        );
+    }
+
+    #[test]
+    fn sorts_by_time_to_solve() {
+        let leaderboard = get_mock_leaderboard();
+        assert_eq!(
+            names(&leaderboard, SortOrder::Time, Some(1)),
+            vec!["(anonymous user #2)", "alice", "carol"]
+        );
+        assert_eq!(
+            names(&leaderboard, SortOrder::Time, Some(2)),
+            vec!["alice", "(anonymous user #2)", "carol"]
+        );
+    }
+
+    #[test]
+    fn renders_overview() {
+        let rendered = render_overview(&get_mock_leaderboard(), SortOrder::Score);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].ends_with("(anonymous user #2)     12      2  ★·"));
+        assert!(lines[2].ends_with("alice                   10      3  ★☆"));
+        assert!(lines[3].ends_with("carol                    0      0  ··"));
+    }
+
+    #[test]
+    fn renders_day() {
+        let rendered = render_day(&get_mock_leaderboard(), 1, SortOrder::Time);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert!(lines[1].contains("2023-12-01 00:02:00    00:02:00"));
+        assert!(lines[1].contains("2023-12-01 00:10:00    00:10:00"));
+        assert!(lines[2].contains("00:05:00"));
+        assert!(lines[2].contains("00:15:00"));
+    }
+}
@@ -0,0 +1,392 @@
+/// Minimal markdown renderer for puzzle descriptions downloaded by aoc-cli.
+/// Supports the subset used by the generated files: headings, paragraphs, lists, code blocks,
+/// emphasis, inline code, links and backslash escapes.
+use crate::template::{ANSI_BOLD, ANSI_CYAN, ANSI_ITALIC, ANSI_RESET, ANSI_UNDERLINE};
+
+const CODE_GUTTER: &str = "  │ ";
+
+#[derive(Default)]
+struct Style {
+    bold: bool,
+    italic: bool,
+    code: bool,
+    link: bool,
+}
+
+impl Style {
+    fn escape_codes(&self) -> String {
+        let mut s = String::from(ANSI_RESET);
+        if self.bold {
+            s.push_str(ANSI_BOLD);
+        }
+        if self.italic {
+            s.push_str(ANSI_ITALIC);
+        }
+        if self.code {
+            s.push_str(ANSI_CYAN);
+        }
+        if self.link {
+            s.push_str(ANSI_UNDERLINE);
+        }
+        s
+    }
+}
+
+/// Renders inline markdown to a single line of ANSI-styled text.
+fn render_inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut style = Style::default();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let prev = i.checked_sub(1).map(|j| chars[j]);
+        let next = chars.get(i + 1).copied();
+
+        match c {
+            '\\' if next.is_some_and(|n| n.is_ascii_punctuation()) => {
+                out.push(chars[i + 1]);
+                i += 2;
+            }
+            '`' => match chars[i + 1..].iter().position(|&x| x == '`') {
+                Some(len) => {
+                    style.code = true;
+                    out.push_str(&style.escape_codes());
+                    out.extend(&chars[i + 1..i + 1 + len]);
+                    style.code = false;
+                    out.push_str(&style.escape_codes());
+                    i += len + 2;
+                }
+                None => {
+                    out.push(c);
+                    i += 1;
+                }
+            },
+            '*' if next == Some('*') => {
+                style.bold = !style.bold;
+                out.push_str(&style.escape_codes());
+                i += 2;
+            }
+            // emphasis only opens at the start of a word, so that `2 * 3`, `a*b` and `snake_case` stay as they are.
+            '*' | '_'
+                if !style.italic
+                    && !prev.is_some_and(char::is_alphanumeric)
+                    && next.is_some_and(|n| !n.is_whitespace())
+                    || style.italic && !next.is_some_and(char::is_alphanumeric) =>
+            {
+                style.italic = !style.italic;
+                out.push_str(&style.escape_codes());
+                i += 1;
+            }
+            '[' => match parse_link(&chars[i..]) {
+                Some((label, len)) => {
+                    style.link = true;
+                    out.push_str(&style.escape_codes());
+                    out.push_str(&render_inline(&label));
+                    style.link = false;
+                    out.push_str(&style.escape_codes());
+                    i += len;
+                }
+                None => {
+                    out.push(c);
+                    i += 1;
+                }
+            },
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    if style.bold || style.italic {
+        out.push_str(ANSI_RESET);
+    }
+
+    out
+}
+
+/// Parses `[label](url)` at the start of `chars`, returning the label and the consumed length.
+fn parse_link(chars: &[char]) -> Option<(String, usize)> {
+    let label_end = chars.iter().position(|&c| c == ']')?;
+    if chars.get(label_end + 1) != Some(&'(') {
+        return None;
+    }
+    let url_len = chars[label_end + 2..].iter().position(|&c| c == ')')?;
+    let label = chars[1..label_end].iter().collect();
+    Some((label, label_end + 3 + url_len))
+}
+
+/// Counts the characters of `s` that take up space in the terminal.
+fn visible_len(s: &str) -> usize {
+    let mut len = 0;
+    let mut in_escape = false;
+    for c in s.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if in_escape => {}
+            _ => len += 1,
+        }
+    }
+    len
+}
+
+/// Greedily wraps styled text to `width`, prefixing the first line with `first_prefix` and
+/// every following line with `prefix`.
+fn wrap(text: &str, width: usize, first_prefix: &str, prefix: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first_prefix.to_string();
+    let mut line_len = visible_len(first_prefix);
+    let mut is_empty = true;
+
+    for word in text.split(' ').filter(|w| !w.is_empty()) {
+        let word_len = visible_len(word);
+        if !is_empty && line_len + 1 + word_len > width {
+            lines.push(line);
+            line = prefix.to_string();
+            line_len = visible_len(prefix);
+            is_empty = true;
+        }
+        if !is_empty {
+            line.push(' ');
+            line_len += 1;
+        }
+        line.push_str(word);
+        line_len += word_len;
+        is_empty = false;
+    }
+
+    lines.push(line);
+    lines
+}
+
+fn is_fence(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+fn is_rule(line: &str, c: char) -> bool {
+    let trimmed = line.trim();
+    trimmed.len() >= 3 && trimmed.chars().all(|x| x == c)
+}
+
+/// Splits a list item into its marker and content, e.g. `* foo` or `1. foo`.
+fn parse_list_item(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    let (marker, rest) = trimmed.split_once(' ')?;
+    let is_bullet = matches!(marker, "*" | "-" | "+");
+    let is_ordered = marker.len() > 1
+        && marker.ends_with('.')
+        && marker[..marker.len() - 1]
+            .chars()
+            .all(|c| c.is_ascii_digit());
+    if is_bullet || is_ordered {
+        Some((marker, rest.trim_start()))
+    } else {
+        None
+    }
+}
+
+fn render_heading(text: &str, width: usize) -> Vec<String> {
+    let styled = format!(
+        "{ANSI_BOLD}{ANSI_UNDERLINE}{}{ANSI_RESET}",
+        render_inline(text.trim())
+    );
+    wrap(&styled, width, "", "")
+}
+
+#[derive(Default)]
+struct Renderer {
+    width: usize,
+    lines: Vec<String>,
+    paragraph: Vec<String>,
+    item: Option<(String, Vec<String>)>,
+}
+
+impl Renderer {
+    fn blank(&mut self) {
+        if self.lines.last().is_some_and(|l| !l.is_empty()) {
+            self.lines.push(String::new());
+        }
+    }
+
+    fn flush(&mut self) {
+        if !self.paragraph.is_empty() {
+            let text = render_inline(&self.paragraph.join(" "));
+            self.lines.extend(wrap(&text, self.width, "", ""));
+            self.paragraph.clear();
+        }
+        if let Some((marker, content)) = self.item.take() {
+            let bullet = if marker.ends_with('.') {
+                marker
+            } else {
+                "•".into()
+            };
+            let first_prefix = format!("  {bullet} ");
+            let prefix = " ".repeat(visible_len(&first_prefix));
+            let text = render_inline(&content.join(" "));
+            self.lines
+                .extend(wrap(&text, self.width, &first_prefix, &prefix));
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        if line.trim().is_empty() {
+            self.flush();
+            self.blank();
+        } else if let Some(text) = line.strip_prefix('#') {
+            self.flush();
+            self.blank();
+            let heading = render_heading(text.trim_start_matches('#'), self.width);
+            self.lines.extend(heading);
+            self.lines.push(String::new());
+        } else if !self.paragraph.is_empty() && (is_rule(line, '=') || is_rule(line, '-')) {
+            let text = self.paragraph.join(" ");
+            self.paragraph.clear();
+            self.flush();
+            self.blank();
+            let heading = render_heading(&text, self.width);
+            self.lines.extend(heading);
+            self.lines.push(String::new());
+        } else if is_rule(line, '-') || is_rule(line, '*') {
+            self.flush();
+            self.lines.push("─".repeat(self.width));
+        } else if let Some((marker, content)) = parse_list_item(line) {
+            self.flush();
+            self.item = Some((marker.to_string(), vec![content.to_string()]));
+        } else if let Some((_, content)) = self.item.as_mut() {
+            content.push(line.trim().to_string());
+        } else {
+            self.paragraph.push(line.trim().to_string());
+        }
+    }
+}
+
+/// Renders markdown to ANSI-styled text wrapped to `width` columns.
+#[must_use]
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut renderer = Renderer {
+        width,
+        ..Renderer::default()
+    };
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        if is_fence(line) {
+            renderer.flush();
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            renderer
+                .lines
+                .push(format!("{CODE_GUTTER}{ANSI_CYAN}{line}{ANSI_RESET}"));
+        } else {
+            renderer.push_line(line);
+        }
+    }
+
+    renderer.flush();
+
+    while renderer.lines.last().is_some_and(String::is_empty) {
+        renderer.lines.pop();
+    }
+
+    renderer.lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, render_inline, visible_len, wrap};
+    use crate::template::{ANSI_BOLD, ANSI_CYAN, ANSI_ITALIC, ANSI_RESET};
+
+    fn strip_ansi(s: &str) -> String {
+        let mut out = String::new();
+        let mut in_escape = false;
+        for c in s.chars() {
+            match c {
+                '\x1b' => in_escape = true,
+                'm' if in_escape => in_escape = false,
+                _ if in_escape => {}
+                _ => out.push(c),
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn renders_emphasis() {
+        let s = render_inline("a *b* **c**");
+        assert!(s.contains(&format!("{ANSI_RESET}{ANSI_ITALIC}b")));
+        assert!(s.contains(&format!("{ANSI_RESET}{ANSI_BOLD}c")));
+        assert_eq!(strip_ansi(&s), "a b c");
+    }
+
+    #[test]
+    fn keeps_arithmetic_stars() {
+        let s = render_inline("it takes 2 * 3 = 6 steps, or a*b in *general*");
+        assert_eq!(s.matches(ANSI_ITALIC).count(), 1);
+        assert!(s.contains(&format!("{ANSI_RESET}{ANSI_ITALIC}general")));
+        assert_eq!(
+            strip_ansi(&s),
+            "it takes 2 * 3 = 6 steps, or a*b in general"
+        );
+    }
+
+    #[test]
+    fn renders_inline_code_and_links() {
+        let s = render_inline("use `a*b` and [the site](https://adventofcode.com)");
+        assert!(s.contains(&format!("{ANSI_CYAN}a*b")));
+        assert_eq!(strip_ansi(&s), "use a*b and the site");
+    }
+
+    #[test]
+    fn renders_escapes() {
+        assert_eq!(render_inline(r"\--- Day 1 \---"), "--- Day 1 ---");
+    }
+
+    #[test]
+    fn measures_visible_length() {
+        assert_eq!(visible_len(&format!("{ANSI_BOLD}abc{ANSI_RESET}")), 3);
+    }
+
+    #[test]
+    fn wraps_words() {
+        let lines = wrap("aaa bbb ccc ddd", 8, "", "");
+        assert_eq!(lines, vec!["aaa bbb", "ccc ddd"]);
+        let lines = wrap("aaa bbb ccc", 9, "  • ", "    ");
+        assert_eq!(lines, vec!["  • aaa", "    bbb", "    ccc"]);
+    }
+
+    #[test]
+    fn renders_document() {
+        let md = [
+            r"\--- Day 1: Trebuchet?! ---",
+            "----------",
+            "",
+            "Some text that",
+            "continues here.",
+            "",
+            "```",
+            "1abc2",
+            "```",
+            "",
+            "* an item",
+        ]
+        .join("\n");
+        let rendered = strip_ansi(&render(&md, 80));
+        let expected = [
+            "--- Day 1: Trebuchet?! ---",
+            "",
+            "Some text that continues here.",
+            "",
+            "  │ 1abc2",
+            "",
+            "  • an item",
+        ]
+        .join("\n");
+        assert_eq!(rendered, expected);
+    }
+}
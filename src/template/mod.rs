@@ -1,34 +1,49 @@
 use std::{env, fs};
 
 pub mod aoc_cli;
+pub mod client;
 pub mod commands;
+pub mod day;
+pub mod inputs;
+pub mod leaderboard;
+pub mod markdown;
+pub mod readme;
 pub mod readme_benchmarks;
+pub mod readme_stars;
 pub mod runner;
+pub mod session;
+pub mod unlock;
+
+pub use day::Day;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
+pub const ANSI_UNDERLINE: &str = "\x1b[4m";
+pub const ANSI_CYAN: &str = "\x1b[36m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
-#[must_use] pub fn read_file(folder: &str, day: u8) -> String {
+/// Line endings and trailing newlines are normalised, see [`inputs::normalize`].
+#[must_use]
+pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day:02}.txt"));
+    let filepath = cwd.join(day.data_path(folder));
     let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    inputs::normalize(&f.expect("could not open input file"))
 }
 
 /// main! produces a block setting up the input and runner for each part.
+/// It also defines a `DAY` constant that tests can use to read example files.
 #[macro_export]
 macro_rules! main {
     ($day:expr) => {
+        const DAY: advent_of_code::template::Day = advent_of_code::day!($day);
+
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", $day);
-            run_part(part_one, &input, $day, 1);
-            run_part(part_two, &input, $day, 2);
+            let input = advent_of_code::template::read_file("inputs", DAY);
+            run_part(part_one, &input, DAY, 1);
+            run_part(part_two, &input, DAY, 2);
         }
     };
 }
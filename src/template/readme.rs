@@ -0,0 +1,97 @@
+/// Module that updates sections of the readme that are delimited by marker comments.
+/// The approach taken is similar to how `aoc-readme-stars` handles this.
+use std::{fs, io};
+
+static README_PATH: &str = "README.md";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+struct SectionPosition {
+    pos_start: usize,
+    pos_end: usize,
+}
+
+fn locate_section(readme: &str, marker: &str) -> Result<SectionPosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
+
+    if matches.len() > 2 {
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
+    }
+
+    let pos_start = matches
+        .first()
+        .map(|m| m.0)
+        .ok_or_else(|| Error::Parser("Could not find section start position.".into()))?;
+
+    let pos_end = matches
+        .last()
+        .map(|m| m.0 + m.1.len())
+        .ok_or_else(|| Error::Parser("Could not find section end position.".into()))?;
+
+    Ok(SectionPosition { pos_start, pos_end })
+}
+
+/// Replaces the section delimited by `marker` with `lines`, surrounded by the marker.
+pub fn update_section(s: &mut String, marker: &str, lines: &[String]) -> Result<(), Error> {
+    let positions = locate_section(s, marker)?;
+    let section = [marker]
+        .into_iter()
+        .chain(lines.iter().map(String::as_str))
+        .chain([marker])
+        .collect::<Vec<_>>()
+        .join("\n");
+    s.replace_range(positions.pos_start..positions.pos_end, &section);
+    Ok(())
+}
+
+/// Reads the readme, applies `f` to its contents and writes the result back.
+pub fn update_file(f: impl FnOnce(&mut String) -> Result<(), Error>) -> Result<(), Error> {
+    let mut readme = String::from_utf8_lossy(&fs::read(README_PATH)?).to_string();
+    f(&mut readme)?;
+    fs::write(README_PATH, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::update_section;
+
+    const MARKER: &str = "<!--- test --->";
+
+    #[test]
+    fn updates_section_between_markers() {
+        let mut s = format!("foo\n{MARKER}\nold\n{MARKER}\nbar");
+        update_section(&mut s, MARKER, &["new".into()]).unwrap();
+        assert_eq!(s, format!("foo\n{MARKER}\nnew\n{MARKER}\nbar"));
+    }
+
+    #[test]
+    fn expands_single_marker() {
+        let mut s = format!("foo\n{MARKER}\nbar");
+        update_section(&mut s, MARKER, &["new".into()]).unwrap();
+        assert_eq!(s, format!("foo\n{MARKER}\nnew\n{MARKER}\nbar"));
+    }
+
+    #[test]
+    fn leaves_other_sections_untouched() {
+        let other = "<!--- other --->";
+        let mut s = format!("{other}\nkeep\n{other}\n{MARKER}{MARKER}");
+        update_section(&mut s, MARKER, &["new".into()]).unwrap();
+        assert_eq!(
+            s,
+            format!("{other}\nkeep\n{other}\n{MARKER}\nnew\n{MARKER}")
+        );
+    }
+}
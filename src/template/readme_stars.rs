@@ -0,0 +1,150 @@
+/// Module that updates the readme with a table of collected stars.
+/// Stars are read from the solved markers aoc-cli writes to the puzzle files once a part is solved.
+/// The output mirrors the table generated by the `advent-readme-stars` action.
+use std::fs;
+
+use crate::template::{
+    aoc_cli,
+    readme::{self, Error},
+    unlock, Day,
+};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+static SOLVED_MARKER: &str = "Your puzzle answer was";
+static STAR: &str = "⭐";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stars {
+    pub day: Day,
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+/// Counts solved parts in a puzzle description.
+#[must_use]
+pub fn parse_stars(day: Day, puzzle: &str) -> Stars {
+    let solved = puzzle.matches(SOLVED_MARKER).count();
+    Stars {
+        day,
+        part_1: solved >= 1,
+        part_2: solved >= 2,
+    }
+}
+
+/// Collects stars for all days that have a downloaded puzzle with at least one solved part.
+#[must_use]
+pub fn collect_stars() -> Vec<Stars> {
+    Day::all()
+        .filter_map(|day| {
+            let puzzle = fs::read_to_string(day.puzzle_path()).ok()?;
+            Some(parse_stars(day, &puzzle))
+        })
+        .filter(|stars| stars.part_1)
+        .collect()
+}
+
+fn construct_table(prefix: &str, year: u16, stars: &[Stars]) -> Vec<String> {
+    let mut lines: Vec<String> = vec![
+        format!("{prefix} {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for day in stars {
+        let star = |solved: bool| if solved { STAR } else { " " };
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {} | {} |",
+            day.day,
+            day.day,
+            star(day.part_1),
+            star(day.part_2)
+        ));
+    }
+
+    lines
+}
+
+fn update_content(s: &mut String, year: u16, stars: &[Stars]) -> Result<(), Error> {
+    let table = construct_table("##", year, stars);
+    readme::update_section(s, MARKER, &table)
+}
+
+pub fn update(stars: &[Stars]) -> Result<(), Error> {
+    let year = aoc_cli::get_year().unwrap_or_else(unlock::current_year);
+    readme::update_file(|s| update_content(s, year, stars))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_stars, update_content, Stars, MARKER};
+    use crate::day;
+
+    #[test]
+    fn parses_solved_markers() {
+        let unsolved = "## --- Day 1 ---\nSome text.";
+        assert_eq!(
+            parse_stars(day!(1), unsolved),
+            Stars {
+                day: day!(1),
+                part_1: false,
+                part_2: false
+            }
+        );
+
+        let one = "Your puzzle answer was `142`.\n\nThe first half of this puzzle is complete!";
+        assert_eq!(
+            parse_stars(day!(2), one),
+            Stars {
+                day: day!(2),
+                part_1: true,
+                part_2: false
+            }
+        );
+
+        let both =
+            "Your puzzle answer was `142`.\n\n## --- Part Two ---\n\nYour puzzle answer was `281`.";
+        assert_eq!(
+            parse_stars(day!(3), both),
+            Stars {
+                day: day!(3),
+                part_1: true,
+                part_2: true
+            }
+        );
+    }
+
+    #[test]
+    fn format_stars() {
+        let stars = [
+            Stars {
+                day: day!(1),
+                part_1: true,
+                part_2: true,
+            },
+            Stars {
+                day: day!(2),
+                part_1: true,
+                part_2: false,
+            },
+        ];
+        let mut s = format!("foo\n{MARKER}\n\n<!--- benchmarking table --->\nbaz");
+        update_content(&mut s, 2023, &stars).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "",
+            "<!--- benchmarking table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
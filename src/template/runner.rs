@@ -1,5 +1,5 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
 use std::fmt::Display;
 use std::io::{stdout, Write};
 use std::process::Output;
@@ -8,7 +8,7 @@ use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: u8, part: u8) {
+pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
     let (result, duration, samples) =
@@ -17,10 +17,24 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        if let Some(Err(e)) = submit_result(result, day, part) {
+            aoc_cli::exit_with_error(&e);
+        }
     }
 }
 
+/// Benches `func` on `input` like `solve --time` does for a solution part, and prints the result and
+/// average duration under `label`. Used to compare implementations on real inputs, e.g. in `benches/ints.rs`.
+pub fn run_bench<I: Clone, T: Display>(label: &str, func: impl Fn(I) -> T, input: I) {
+    let timer = Instant::now();
+    let result = Some(func(input.clone()));
+    let base_time = timer.elapsed();
+
+    print_result(&result, label, "");
+    let (duration, samples) = bench(func, input, &base_time);
+    print_result(&result, label, &format_duration(&duration, samples));
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
@@ -131,7 +145,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 ///  2. aoc-cli is installed.
 fn submit_result<T: Display>(
     result: T,
-    day: u8,
+    day: Day,
     part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
@@ -0,0 +1,185 @@
+/// Module that stores and looks up the Advent of Code session cookie.
+/// Uses the same locations as aoc-cli, so both tools share one session.
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+static SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+static HOME_SESSION_FILE: &str = ".adventofcode.session";
+static CONFIG_SESSION_FILE: &str = "adventofcode.session";
+static REDACTED: &str = "<redacted>";
+
+/// Session cookies are valid for about a month.
+pub const SESSION_LIFETIME: Duration = Duration::from_secs(30 * 24 * 3600);
+/// Warn about expiry once less than this much time is left.
+pub const EXPIRY_WARNING: Duration = Duration::from_secs(5 * 24 * 3600);
+
+pub struct Session {
+    pub token: String,
+    /// File the token was read from, `None` if it came from the environment.
+    pub path: Option<PathBuf>,
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// The user's config directory, following platform conventions.
+#[must_use]
+pub fn config_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home_dir().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| home_dir().map(|home| home.join(".config")))
+    }
+}
+
+/// Where `aoc-login` stores the session.
+#[must_use]
+pub fn config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(CONFIG_SESSION_FILE))
+}
+
+/// Accepts a raw token or a copied `session=<token>` cookie.
+#[must_use]
+pub fn normalize_token(s: &str) -> String {
+    let token = s.trim();
+    token.strip_prefix("session=").unwrap_or(token).to_string()
+}
+
+/// Session tokens are hex strings.
+#[must_use]
+pub fn is_well_formed(token: &str) -> bool {
+    !token.is_empty() && token.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Looks up the session in the environment, `~/.adventofcode.session` and the config directory, in this order.
+/// This is the order aoc-cli uses, so that both tools always agree on the session.
+#[must_use]
+pub fn load() -> Option<Session> {
+    if let Ok(token) = env::var(SESSION_ENV) {
+        return Some(Session {
+            token: normalize_token(&token),
+            path: None,
+        });
+    }
+
+    [home_dir().map(|h| h.join(HOME_SESSION_FILE)), config_path()]
+        .into_iter()
+        .flatten()
+        .find_map(|path| {
+            let token = normalize_token(&fs::read_to_string(&path).ok()?);
+            (!token.is_empty()).then_some(Session {
+                token,
+                path: Some(path),
+            })
+        })
+}
+
+/// The session saved in the config directory, if `session` is a different one that takes precedence over it.
+#[must_use]
+pub fn shadowed_by(session: &Session) -> Option<PathBuf> {
+    let path = config_path()?;
+    let token = normalize_token(&fs::read_to_string(&path).ok()?);
+    (session.path.as_ref() != Some(&path) && !token.is_empty() && token != session.token)
+        .then_some(path)
+}
+
+/// Human-readable origin of the session, for messages.
+#[must_use]
+pub fn describe_source(session: &Session) -> String {
+    match &session.path {
+        Some(path) => format!("\"{}\"", path.display()),
+        None => format!("the {SESSION_ENV} environment variable"),
+    }
+}
+
+/// Writes the session to the config directory, readable only by the current user.
+pub fn store(token: &str) -> Result<PathBuf, io::Error> {
+    let path = config_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory found"))?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(&path)?;
+
+    // `mode` only applies to new files, so tighten permissions of existing ones as well.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+
+    writeln!(file, "{token}")?;
+    Ok(path)
+}
+
+/// Estimated time until the session expires, based on when it was saved.
+/// Returns `None` if the session did not come from a file.
+#[must_use]
+pub fn time_until_expiry(session: &Session) -> Option<Duration> {
+    let modified = fs::metadata(session.path.as_ref()?).ok()?.modified().ok()?;
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default();
+    Some(SESSION_LIFETIME.saturating_sub(age))
+}
+
+/// Replaces every occurrence of the session token in `s`.
+#[must_use]
+pub fn redact(s: &str, token: &str) -> String {
+    if token.is_empty() {
+        s.to_string()
+    } else {
+        s.replace(token, REDACTED)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_well_formed, normalize_token, redact};
+
+    #[test]
+    fn normalizes_tokens() {
+        assert_eq!(normalize_token(" 53616c7465640a \n"), "53616c7465640a");
+        assert_eq!(normalize_token("session=53616c7465640a"), "53616c7465640a");
+    }
+
+    #[test]
+    fn validates_token_format() {
+        assert!(is_well_formed("53616c7465640a"));
+        assert!(!is_well_formed(""));
+        assert!(!is_well_formed("not a token"));
+    }
+
+    #[test]
+    fn redacts_tokens() {
+        let token = "53616c7465640a";
+        assert_eq!(
+            redact(&format!("Cookie: session={token}; {token}"), token),
+            "Cookie: session=<redacted>; <redacted>"
+        );
+        assert_eq!(redact("nothing to hide", ""), "nothing to hide");
+    }
+}
@@ -0,0 +1,147 @@
+/// Helpers to compute when a puzzle unlocks. Puzzles unlock at midnight EST (UTC-5).
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const UNLOCK_OFFSET_SECONDS: i64 = 5 * 3600;
+
+/// Days since the unix epoch for a date in the proleptic gregorian calendar.
+/// See: <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Date for a day count since the unix epoch.
+/// See: <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400;
+    if month <= 2 {
+        (year + 1, month, day)
+    } else {
+        (year, month, day)
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => i64::try_from(d.as_secs()).unwrap_or(i64::MAX),
+        Err(e) => -i64::try_from(e.duration().as_secs()).unwrap_or(i64::MAX),
+    }
+}
+
+/// Unix timestamp at which the puzzle for `day` of `year` unlocks.
+#[must_use]
+pub fn unlock_timestamp(year: u16, day: u8) -> i64 {
+    days_from_civil(i64::from(year), 12, i64::from(day)) * SECONDS_PER_DAY + UNLOCK_OFFSET_SECONDS
+}
+
+/// The event year as seen from the puzzle timezone.
+#[must_use]
+pub fn current_year() -> u16 {
+    let days =
+        (unix_seconds(SystemTime::now()) - UNLOCK_OFFSET_SECONDS).div_euclid(SECONDS_PER_DAY);
+    u16::try_from(civil_from_days(days).0).unwrap_or(u16::MAX)
+}
+
+/// Formats a unix timestamp as date and time in the puzzle timezone.
+#[must_use]
+pub fn format_timestamp(ts: i64) -> String {
+    let local = ts - UNLOCK_OFFSET_SECONDS;
+    let (year, month, day) = civil_from_days(local.div_euclid(SECONDS_PER_DAY));
+    let secs = local.rem_euclid(SECONDS_PER_DAY);
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+/// Time left until the puzzle unlocks, or `None` if it is already available.
+#[must_use]
+pub fn time_until_unlock(year: u16, day: u8) -> Option<Duration> {
+    let remaining = unlock_timestamp(year, day) - unix_seconds(SystemTime::now());
+    u64::try_from(remaining)
+        .ok()
+        .filter(|&s| s > 0)
+        .map(Duration::from_secs)
+}
+
+#[must_use]
+pub fn format_countdown(duration: &Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes, seconds) = (
+        secs / 86_400,
+        secs % 86_400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        civil_from_days, days_from_civil, format_countdown, format_timestamp, unlock_timestamp,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn computes_days_from_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(2023, 12, 1), 19692);
+    }
+
+    #[test]
+    fn computes_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19692), (2023, 12, 1));
+        assert_eq!(
+            civil_from_days(days_from_civil(2024, 1, 1) - 1),
+            (2023, 12, 31)
+        );
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    }
+
+    #[test]
+    fn computes_unlock_timestamp() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(unlock_timestamp(2023, 1), 1_701_406_800);
+        // 2023-12-25T05:00:00Z
+        assert_eq!(unlock_timestamp(2023, 25), 1_703_480_400);
+    }
+
+    #[test]
+    fn formats_timestamp() {
+        assert_eq!(format_timestamp(1_701_406_800), "2023-12-01 00:00:00");
+        assert_eq!(
+            format_timestamp(1_701_406_800 + 3723),
+            "2023-12-01 01:02:03"
+        );
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(&Duration::from_secs(59)), "00:00:59");
+        assert_eq!(format_countdown(&Duration::from_secs(3661)), "01:01:01");
+        assert_eq!(format_countdown(&Duration::from_secs(90061)), "1d 01:01:01");
+    }
+}
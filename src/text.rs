@@ -0,0 +1,298 @@
+/// Text searching helpers that work on byte slices.
+/// [`MultiMatcher`] finds many patterns at once in a single pass, e.g. digits that are spelled out.
+use std::collections::VecDeque;
+
+/// A match of a pattern in a haystack, with the value the pattern was registered with.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Match<'a, V> {
+    pub start: usize,
+    /// Exclusive end of the match.
+    pub end: usize,
+    pub value: &'a V,
+}
+
+// derived impls would require `V: Copy`, but a match only holds a reference.
+impl<V> Clone for Match<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for Match<'_, V> {}
+
+/// An Aho–Corasick automaton built from a table of patterns and their values.
+/// Searches run in time linear in the length of the haystack, independent of the number of patterns.
+pub struct MultiMatcher<V> {
+    patterns: Vec<(usize, V)>,
+    forward: Automaton,
+    /// Built from the reversed patterns, so that rightmost matches can be found from the end.
+    backward: Automaton,
+    max_len: usize,
+}
+
+impl<V> MultiMatcher<V> {
+    /// Builds a matcher from `(pattern, value)` pairs.
+    /// Empty patterns never match. If a pattern appears more than once, its first value is used.
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = (P, V)>) -> Self {
+        let mut bytes: Vec<Vec<u8>> = vec![];
+        let mut table = vec![];
+
+        for (pattern, value) in patterns {
+            let pattern = pattern.as_ref();
+            if !pattern.is_empty() && !bytes.iter().any(|b| b == pattern) {
+                bytes.push(pattern.to_vec());
+                table.push((pattern.len(), value));
+            }
+        }
+
+        let reversed: Vec<Vec<u8>> = bytes
+            .iter()
+            .map(|b| b.iter().rev().copied().collect())
+            .collect();
+
+        MultiMatcher {
+            max_len: bytes.iter().map(Vec::len).max().unwrap_or(0),
+            forward: Automaton::new(&bytes),
+            backward: Automaton::new(&reversed),
+            patterns: table,
+        }
+    }
+
+    /// Number of distinct patterns.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    fn to_match(&self, pattern: usize, end: usize) -> Match<'_, V> {
+        let (len, value) = &self.patterns[pattern];
+        Match {
+            start: end - len,
+            end,
+            value,
+        }
+    }
+
+    /// All matches, including overlapping ones such as both `one` and `eight` in `oneight`.
+    /// Matches are ordered by their end, and longest first for equal ends.
+    pub fn find_overlapping<'a>(
+        &'a self,
+        haystack: &'a [u8],
+    ) -> impl Iterator<Item = Match<'a, V>> + 'a {
+        self.forward
+            .scan(haystack.iter().copied())
+            .flat_map(move |(i, outputs)| outputs.iter().map(move |&p| self.to_match(p, i + 1)))
+    }
+
+    /// The match that starts first, preferring the longest one if several start at the same position.
+    #[must_use]
+    pub fn find_leftmost(&self, haystack: &[u8]) -> Option<Match<'_, V>> {
+        let mut best: Option<Match<V>> = None;
+
+        for (i, outputs) in self.forward.scan(haystack.iter().copied()) {
+            // matches ending at `i` or later start at `i + 1 - max_len` at the earliest,
+            // so once that is past the best start, no earlier or longer match can follow.
+            if best.is_some_and(|m| i + 1 > m.start + self.max_len) {
+                break;
+            }
+            // outputs are sorted longest first, so the first one starts earliest.
+            if let Some(&p) = outputs.first() {
+                let found = self.to_match(p, i + 1);
+                if best.is_none_or(|m| {
+                    found.start < m.start || (found.start == m.start && found.end > m.end)
+                }) {
+                    best = Some(found);
+                }
+            }
+        }
+
+        best
+    }
+
+    /// The match that starts last, preferring the longest one if several start at the same position.
+    #[must_use]
+    pub fn find_rightmost(&self, haystack: &[u8]) -> Option<Match<'_, V>> {
+        // scanning the reversed haystack finds matches by their start, last start first.
+        let (i, outputs) = self
+            .backward
+            .scan(haystack.iter().rev().copied())
+            .find(|(_, outputs)| !outputs.is_empty())?;
+
+        let (len, value) = &self.patterns[outputs[0]];
+        let start = haystack.len() - 1 - i;
+        Some(Match {
+            start,
+            end: start + len,
+            value,
+        })
+    }
+}
+
+/// A dense automaton with a transition for every state and byte.
+struct Automaton {
+    transitions: Vec<usize>,
+    /// Patterns that end in each state, longest first.
+    outputs: Vec<Vec<usize>>,
+}
+
+impl Automaton {
+    const ROOT: usize = 0;
+
+    fn new(patterns: &[Vec<u8>]) -> Self {
+        // build the trie, with 0 as the missing transition. The root is never a target.
+        let mut transitions = vec![0; 256];
+        let mut outputs = vec![vec![]];
+
+        for (index, pattern) in patterns.iter().enumerate() {
+            let mut state = Self::ROOT;
+            for &byte in pattern {
+                let next = transitions[state * 256 + usize::from(byte)];
+                state = if next == Self::ROOT {
+                    let new = outputs.len();
+                    transitions[state * 256 + usize::from(byte)] = new;
+                    transitions.extend([0; 256]);
+                    outputs.push(vec![]);
+                    new
+                } else {
+                    next
+                };
+            }
+            outputs[state].push(index);
+        }
+
+        // fill in the missing transitions from the failure links, breadth-first.
+        let mut fail = vec![Self::ROOT; outputs.len()];
+        let mut queue: VecDeque<usize> = (0..256)
+            .map(|byte| transitions[byte])
+            .filter(|&s| s != Self::ROOT)
+            .collect();
+
+        while let Some(state) = queue.pop_front() {
+            let inherited = outputs[fail[state]].clone();
+            outputs[state].extend(inherited);
+
+            for byte in 0..256 {
+                let next = transitions[state * 256 + byte];
+                let fallback = transitions[fail[state] * 256 + byte];
+                if next == Self::ROOT {
+                    transitions[state * 256 + byte] = fallback;
+                } else {
+                    fail[next] = fallback;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        let lengths: Vec<usize> = patterns.iter().map(Vec::len).collect();
+        for out in &mut outputs {
+            out.sort_by_key(|&p| std::cmp::Reverse(lengths[p]));
+        }
+
+        Automaton {
+            transitions,
+            outputs,
+        }
+    }
+
+    /// Feeds `bytes` through the automaton, yielding each index with the patterns ending there.
+    fn scan<'a>(
+        &'a self,
+        bytes: impl Iterator<Item = u8> + 'a,
+    ) -> impl Iterator<Item = (usize, &'a [usize])> + 'a {
+        bytes.enumerate().scan(Self::ROOT, |state, (i, byte)| {
+            *state = self.transitions[*state * 256 + usize::from(byte)];
+            Some((i, self.outputs[*state].as_slice()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MultiMatcher;
+
+    fn digits() -> MultiMatcher<u32> {
+        let words = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        MultiMatcher::new(words.iter().zip(1..))
+    }
+
+    fn spans(matcher: &MultiMatcher<u32>, haystack: &str) -> Vec<(usize, usize, u32)> {
+        matcher
+            .find_overlapping(haystack.as_bytes())
+            .map(|m| (m.start, m.end, *m.value))
+            .collect()
+    }
+
+    #[test]
+    fn finds_overlapping_matches() {
+        let matcher = digits();
+        assert_eq!(
+            spans(&matcher, "xtwone3eightwo"),
+            [(1, 4, 2), (3, 6, 1), (7, 12, 8), (11, 14, 2)]
+        );
+        assert_eq!(spans(&matcher, "abc"), []);
+    }
+
+    #[test]
+    fn finds_patterns_inside_other_patterns() {
+        let matcher = MultiMatcher::new([("he", 1), ("she", 2), ("his", 3), ("hers", 4)]);
+        assert_eq!(spans(&matcher, "ushers"), [(1, 4, 2), (2, 4, 1), (2, 6, 4)]);
+    }
+
+    #[test]
+    fn finds_leftmost_and_rightmost_matches() {
+        let matcher = digits();
+        let first = |s: &str| matcher.find_leftmost(s.as_bytes()).map(|m| *m.value);
+        let last = |s: &str| matcher.find_rightmost(s.as_bytes()).map(|m| *m.value);
+
+        assert_eq!(
+            (first("zoneight234"), last("zoneight234")),
+            (Some(1), Some(8))
+        );
+        assert_eq!((first("eightwo"), last("eightwo")), (Some(8), Some(2)));
+        assert_eq!((first("7pqrst"), last("7pqrst")), (None, None));
+    }
+
+    #[test]
+    fn prefers_earlier_starts_over_earlier_ends() {
+        let matcher = MultiMatcher::new([("abcd", 1), ("bc", 2), ("cd", 3), ("c", 4)]);
+
+        let leftmost = matcher.find_leftmost(b"xabcd").unwrap();
+        assert_eq!((leftmost.start, leftmost.end, *leftmost.value), (1, 5, 1));
+
+        let rightmost = matcher.find_rightmost(b"xabcd").unwrap();
+        assert_eq!(
+            (rightmost.start, rightmost.end, *rightmost.value),
+            (3, 5, 3)
+        );
+    }
+
+    #[test]
+    fn prefers_longer_matches_at_the_same_start() {
+        for patterns in [[("ab", 1), ("abcd", 2)], [("ab", 1), ("abc", 2)]] {
+            let matcher = MultiMatcher::new(patterns);
+            for haystack in ["abcd", "xabcdx"] {
+                let leftmost = matcher.find_leftmost(haystack.as_bytes()).unwrap();
+                let rightmost = matcher.find_rightmost(haystack.as_bytes()).unwrap();
+                assert_eq!(*leftmost.value, 2, "{patterns:?} in {haystack}");
+                assert_eq!(*rightmost.value, 2, "{patterns:?} in {haystack}");
+            }
+        }
+    }
+
+    #[test]
+    fn ignores_empty_and_duplicate_patterns() {
+        let matcher = MultiMatcher::new([("", 0), ("a", 1), ("a", 2)]);
+        assert_eq!(matcher.len(), 1);
+        assert_eq!(spans(&matcher, "aa"), [(0, 1, 1), (1, 2, 1)]);
+        assert!(MultiMatcher::<u32>::new::<&str>([])
+            .find_leftmost(b"a")
+            .is_none());
+    }
+}
@@ -0,0 +1,331 @@
+/// Disjoint sets for connected-component puzzles, with path compression and union by rank,
+/// so that each operation takes effectively constant time.
+/// [`UnionFind`] works on dense ids `0..len`, [`KeyedUnionFind`] on any hashable keys.
+use std::{collections::HashMap, hash::Hash};
+
+#[derive(Clone, Debug, Default)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    /// Size of each component, only up to date for roots.
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    /// `len` singleton sets with ids `0..len`.
+    #[must_use]
+    pub fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            size: vec![1; len],
+            components: len,
+        }
+    }
+
+    /// Adds a singleton set and returns its id.
+    pub fn add(&mut self) -> usize {
+        let id = self.parent.len();
+        self.parent.push(id);
+        self.rank.push(0);
+        self.size.push(1);
+        self.components += 1;
+        id
+    }
+
+    /// Number of elements.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of disjoint sets.
+    #[must_use]
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// The representative of the set containing `id`.
+    ///
+    /// # Panics
+    /// If `id` is out of bounds.
+    pub fn find(&mut self, id: usize) -> usize {
+        let mut root = id;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // point everything on the way directly at the root.
+        let mut current = id;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, returning whether they were separate.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        // attach the shallower tree below the deeper one.
+        let (root, child) = if self.rank[a] < self.rank[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        if self.rank[root] == self.rank[child] {
+            self.rank[root] += 1;
+        }
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+        self.components -= 1;
+        true
+    }
+
+    #[must_use]
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the set containing `id`.
+    pub fn size(&mut self, id: usize) -> usize {
+        let root = self.find(id);
+        self.size[root]
+    }
+
+    /// All sets, each sorted by id, in order of their smallest id.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index: HashMap<usize, usize> = HashMap::new();
+        let mut components: Vec<Vec<usize>> = vec![];
+
+        for id in 0..self.len() {
+            let root = self.find(id);
+            let i = *index.entry(root).or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+            components[i].push(id);
+        }
+
+        components
+    }
+
+    /// Sizes of all sets, in the same order as [`Self::components`].
+    pub fn component_sizes(&mut self) -> Vec<usize> {
+        self.components().iter().map(Vec::len).collect()
+    }
+}
+
+/// A [`UnionFind`] over hashable keys. Keys are given dense ids in the order they are first seen.
+#[derive(Clone, Debug)]
+pub struct KeyedUnionFind<K> {
+    ids: HashMap<K, usize>,
+    keys: Vec<K>,
+    sets: UnionFind,
+}
+
+impl<K> Default for KeyedUnionFind<K> {
+    fn default() -> Self {
+        KeyedUnionFind {
+            ids: HashMap::new(),
+            keys: vec![],
+            sets: UnionFind::default(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash> KeyedUnionFind<K> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `key` as a singleton set if it is new, and returns its id.
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(&id) = self.ids.get(&key) {
+            return id;
+        }
+        let id = self.sets.add();
+        self.ids.insert(key.clone(), id);
+        self.keys.push(key);
+        id
+    }
+
+    /// The id of `key`, if it was inserted.
+    #[must_use]
+    pub fn id(&self, key: &K) -> Option<usize> {
+        self.ids.get(key).copied()
+    }
+
+    /// The key with id `id`.
+    #[must_use]
+    pub fn key(&self, id: usize) -> &K {
+        &self.keys[id]
+    }
+
+    /// Number of keys.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    #[must_use]
+    pub fn component_count(&self) -> usize {
+        self.sets.component_count()
+    }
+
+    /// Merges the sets containing `a` and `b`, inserting them if they are new.
+    /// Returns whether they were separate.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.sets.union(a, b)
+    }
+
+    /// The representative key of the set containing `key`.
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let id = self.id(key)?;
+        let root = self.sets.find(id);
+        Some(&self.keys[root])
+    }
+
+    /// Whether both keys were inserted and are in the same set.
+    pub fn connected(&mut self, a: &K, b: &K) -> bool {
+        match (self.id(a), self.id(b)) {
+            (Some(a), Some(b)) => self.sets.connected(a, b),
+            _ => false,
+        }
+    }
+
+    /// Number of keys in the set containing `key`, 0 if it was not inserted.
+    pub fn size(&mut self, key: &K) -> usize {
+        self.id(key).map_or(0, |id| self.sets.size(id))
+    }
+
+    /// All sets, in the order their keys were first seen.
+    pub fn components(&mut self) -> Vec<Vec<&K>> {
+        self.sets
+            .components()
+            .into_iter()
+            .map(|ids| ids.into_iter().map(|id| &self.keys[id]).collect())
+            .collect()
+    }
+
+    /// Sizes of all sets, in the same order as [`Self::components`].
+    pub fn component_sizes(&mut self) -> Vec<usize> {
+        self.sets.component_sizes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyedUnionFind, UnionFind};
+    use proptest::prelude::*;
+
+    #[test]
+    fn merges_sets() {
+        let mut sets = UnionFind::new(6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(4, 1));
+        assert!(!sets.union(0, 4));
+        assert!(sets.union(2, 5));
+
+        assert!(sets.connected(0, 4));
+        assert!(!sets.connected(0, 2));
+        assert_eq!(sets.size(1), 3);
+        assert_eq!(sets.component_count(), 3);
+        assert_eq!(sets.components(), [vec![0, 1, 4], vec![2, 5], vec![3]]);
+        assert_eq!(sets.component_sizes(), [3, 2, 1]);
+
+        let id = sets.add();
+        assert_eq!((id, sets.component_count()), (6, 4));
+    }
+
+    #[test]
+    fn merges_keyed_sets() {
+        let mut sets = KeyedUnionFind::new();
+        for (a, b) in [
+            ("jqt", "rhn"),
+            ("xhk", "hfx"),
+            ("rhn", "xhk"),
+            ("cmg", "qnr"),
+        ] {
+            sets.union(a, b);
+        }
+        sets.insert("lsr");
+
+        assert_eq!(sets.len(), 7);
+        assert!(sets.connected(&"jqt", &"hfx"));
+        assert!(!sets.connected(&"jqt", &"cmg"));
+        assert!(!sets.connected(&"jqt", &"unknown"));
+        assert_eq!(sets.size(&"xhk"), 4);
+        assert_eq!(sets.size(&"unknown"), 0);
+        let root = sets.find(&"hfx").copied();
+        assert_eq!(sets.find(&"jqt").copied(), root);
+        assert_eq!(
+            sets.components(),
+            [
+                vec![&"jqt", &"rhn", &"xhk", &"hfx"],
+                vec![&"cmg", &"qnr"],
+                vec![&"lsr"]
+            ]
+        );
+        assert_eq!(sets.id(&"xhk").map(|id| *sets.key(id)), Some("xhk"));
+    }
+
+    /// Labels each element with the smallest element it is connected to, by propagating until nothing changes.
+    fn model(len: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+        let mut labels: Vec<usize> = (0..len).collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &(a, b) in edges {
+                let label = labels[a].min(labels[b]);
+                if labels[a] != label || labels[b] != label {
+                    (labels[a], labels[b]) = (label, label);
+                    changed = true;
+                }
+            }
+        }
+        labels
+    }
+
+    proptest! {
+        #[test]
+        fn matches_label_propagation(edges in prop::collection::vec((0usize..30, 0usize..30), 0..40)) {
+            let mut sets = UnionFind::new(30);
+            for &(a, b) in &edges {
+                sets.union(a, b);
+            }
+            let labels = model(30, &edges);
+
+            for a in 0..30 {
+                for b in 0..30 {
+                    prop_assert_eq!(sets.connected(a, b), labels[a] == labels[b]);
+                }
+                prop_assert_eq!(sets.size(a), labels.iter().filter(|&&l| l == labels[a]).count());
+            }
+            let mut distinct = labels.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            prop_assert_eq!(sets.component_count(), distinct.len());
+            prop_assert_eq!(sets.components().len(), distinct.len());
+        }
+    }
+}
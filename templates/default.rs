@@ -0,0 +1,26 @@
+pub fn part_one(_input: &str) -> Option<{{type_one}}> {
+    None
+}
+
+pub fn part_two(_input: &str) -> Option<{{type_two}}> {
+    None
+}
+
+advent_of_code::main!({{day}});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+}
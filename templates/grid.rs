@@ -0,0 +1,44 @@
+// {{year}} day {{day}}: {{title}}
+
+use advent_of_code::grid::Grid;
+
+fn parse_grid(input: &str) -> Grid<char> {
+    input.parse().expect("input should be a rectangular grid")
+}
+
+pub fn part_one(input: &str) -> Option<{{type_one}}> {
+    let _grid = parse_grid(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{type_two}}> {
+    let _grid = parse_grid(input);
+    None
+}
+
+advent_of_code::main!({{day}});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_grid() {
+        let grid = parse_grid("ab\ncd");
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[(1, 0)], 'c');
+        assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+    }
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+}